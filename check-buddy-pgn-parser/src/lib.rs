@@ -46,10 +46,10 @@ impl PgnParser {
         for moves in uci_line.iter() {
             let (move1, move2) = (moves[1], moves[2]);
 
-            let uci_move1 = game.board_map.parse_uci_to_move(move1)?;
-            game.board_map.uci_move_turn(uci_move1)?;
-            let uci_move2 = game.board_map.parse_uci_to_move(move2)?;
-            game.board_map.uci_move_turn(uci_move2)?;
+            let uci_move1 = game.current().parse_uci_to_move(move1)?;
+            game.play(uci_move1)?;
+            let uci_move2 = game.current().parse_uci_to_move(move2)?;
+            game.play(uci_move2)?;
         }
 
         Ok(())
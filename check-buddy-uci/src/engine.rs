@@ -0,0 +1,220 @@
+use crate::go::GoOptions;
+use anyhow::{anyhow, Result};
+use check_buddy::position_move::PositionMove;
+use check_buddy::search_result::SearchResult;
+use check_buddy::{BoardMap, Game, Searcher, DEFAULT_HASH_SIZE_MB};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const MAX_HASH_SIZE_MB: usize = 4096;
+
+/// the protocol state between commands. While a search runs, its thread owns the searcher
+/// and hands it back when it's done
+pub(crate) struct Engine {
+    board: BoardMap,
+    /// keys of the positions leading up to `board`, so the search knows about repetitions
+    position_keys: Vec<u64>,
+    /// set by the `UCI_Chess960` option, castling is sent as the king taking its rook
+    chess960: bool,
+    searcher: Option<Searcher>,
+    search: Option<JoinHandle<Searcher>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Engine {
+    pub(crate) fn new() -> Self {
+        let searcher = Searcher::new();
+        Self {
+            board: BoardMap::starting(),
+            position_keys: vec![],
+            chess960: false,
+            stop: searcher.stop_flag(),
+            searcher: Some(searcher),
+            search: None,
+        }
+    }
+
+    /// handles a single line from the GUI, returns false once the engine should quit
+    pub(crate) fn handle(&mut self, line: &str) -> bool {
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        let Some((&command, arguments)) = tokens.split_first() else {
+            return true;
+        };
+        let result = match command {
+            "uci" => {
+                self.uci();
+                Ok(())
+            }
+            "isready" => {
+                println!("readyok");
+                Ok(())
+            }
+            "ucinewgame" => {
+                self.searcher().clear_hash();
+                self.board = BoardMap::starting();
+                self.board.set_chess960(self.chess960);
+                self.position_keys.clear();
+                Ok(())
+            }
+            "position" => self.position(arguments),
+            "go" => self.go(arguments),
+            "stop" => {
+                self.stop();
+                Ok(())
+            }
+            "setoption" => self.set_option(arguments),
+            "quit" => {
+                self.stop();
+                return false;
+            }
+            // unknown commands are ignored, as the protocol asks
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            println!("info string {e}");
+        }
+        true
+    }
+
+    /// stops a running search, which still reports its best move
+    pub(crate) fn stop(&mut self) {
+        if let Some(search) = self.search.take() {
+            self.stop.store(true, Ordering::Relaxed);
+            self.searcher = Some(search.join().expect("search thread panicked"));
+        }
+    }
+
+    fn uci(&self) {
+        println!("id name check-buddy {}", env!("CARGO_PKG_VERSION"));
+        println!("id author {}", env!("CARGO_PKG_AUTHORS"));
+        println!(
+            "option name Hash type spin default {DEFAULT_HASH_SIZE_MB} min 1 max {MAX_HASH_SIZE_MB}"
+        );
+        println!("option name Clear Hash type button");
+        println!("option name UCI_Chess960 type check default false");
+        println!("uciok");
+    }
+
+    /// `position startpos|fen <fen> [moves <move>...]`
+    fn position(&mut self, arguments: &[&str]) -> Result<()> {
+        let (setup, moves) = match arguments.iter().position(|&token| token == "moves") {
+            Some(index) => (&arguments[..index], &arguments[index + 1..]),
+            None => (arguments, &[][..]),
+        };
+        let mut board = match setup.split_first() {
+            Some((&"startpos", _)) => BoardMap::starting(),
+            Some((&"fen", fen)) => {
+                BoardMap::try_from_fen(&fen.join(" ")).map_err(|e| anyhow!("invalid fen: {e}"))?
+            }
+            _ => return Err(anyhow!("position needs startpos or fen")),
+        };
+        // a Chess960 FEN is recognized by itself, the standard setup needs the option
+        if self.chess960 {
+            board.set_chess960(true);
+        }
+        let mut game = Game::new(board);
+        for &text in moves {
+            let position_move = PositionMove::from_uci(text, game.current())
+                .ok()
+                .filter(|position_move| {
+                    game.current().gen_all_legal_moves().contains(position_move)
+                })
+                .ok_or_else(|| anyhow!("illegal move {text}"))?;
+            game.play(position_move)?;
+        }
+        self.board = *game.current();
+        self.position_keys = game.position_keys();
+        Ok(())
+    }
+
+    fn go(&mut self, arguments: &[&str]) -> Result<()> {
+        let options = GoOptions::parse(arguments)?;
+        let limits = options.limits(*self.board.get_active_color());
+        let mut searcher = self.searcher_for_search();
+        searcher.set_history(&self.position_keys);
+        self.stop.store(false, Ordering::Relaxed);
+
+        let board = self.board;
+        let stop = self.stop.clone();
+        self.search = Some(thread::spawn(move || {
+            let result = searcher.search(&board, limits, |result| println!("{}", info(result)));
+            // an infinite search only hands in its move once the GUI asks for it
+            while options.infinite && !stop.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+            }
+            let best_move = result
+                .best_move
+                .map_or("0000".to_string(), |best_move| best_move.to_string());
+            println!("bestmove {best_move}");
+            searcher
+        }));
+        Ok(())
+    }
+
+    /// `setoption name <name> [value <value>]`, names are case insensitive
+    fn set_option(&mut self, arguments: &[&str]) -> Result<()> {
+        let text = arguments.join(" ");
+        let option = text
+            .strip_prefix("name ")
+            .ok_or_else(|| anyhow!("setoption needs a name"))?;
+        let (name, value) = match option.split_once(" value ") {
+            Some((name, value)) => (name, Some(value.trim())),
+            None => (option, None),
+        };
+        match (name.trim().to_ascii_lowercase().as_str(), value) {
+            ("hash", Some(value)) => {
+                let size_mb = value
+                    .parse::<usize>()
+                    .map_err(|_| anyhow!("invalid Hash value {value:?}"))?;
+                self.searcher()
+                    .set_hash_size(size_mb.clamp(1, MAX_HASH_SIZE_MB));
+            }
+            ("clear hash", _) => self.searcher().clear_hash(),
+            ("uci_chess960", Some(value)) => {
+                self.chess960 = value
+                    .parse::<bool>()
+                    .map_err(|_| anyhow!("invalid UCI_Chess960 value {value:?}"))?;
+                self.board.set_chess960(self.chess960);
+            }
+            _ => return Err(anyhow!("unknown option {name}")),
+        }
+        Ok(())
+    }
+
+    /// the searcher, stopping the search that's using it
+    fn searcher(&mut self) -> &mut Searcher {
+        self.stop();
+        self.searcher
+            .as_mut()
+            .expect("searcher is back after stopping")
+    }
+
+    fn searcher_for_search(&mut self) -> Searcher {
+        self.stop();
+        self.searcher
+            .take()
+            .expect("searcher is back after stopping")
+    }
+}
+
+/// `info depth 5 score cp 31 nodes 4210 nps 210500 time 20 hashfull 3 pv e2e4 e7e5 ...`
+fn info(result: &SearchResult) -> String {
+    let score = match result.mate_in() {
+        Some(moves) => format!("mate {moves}"),
+        None => format!("cp {}", result.score),
+    };
+    let millis = result.time.as_millis() as u64;
+    let nps = result.nodes * 1000 / millis.max(1);
+    let pv = result
+        .pv
+        .iter()
+        .map(PositionMove::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "info depth {} score {score} nodes {} nps {nps} time {millis} hashfull {} pv {pv}",
+        result.depth, result.nodes, result.hashfull
+    )
+}
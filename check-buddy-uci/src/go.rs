@@ -0,0 +1,83 @@
+use anyhow::{anyhow, Result};
+use check_buddy::piece_color::PieceColor;
+use check_buddy::search_limits::SearchLimits;
+use std::time::Duration;
+
+/// moves left in the game when the GUI doesn't say
+const DEFAULT_MOVES_TO_GO: u64 = 30;
+/// milliseconds kept on the clock for the GUI and the process to hand the move over
+const MOVE_OVERHEAD_MS: u64 = 50;
+
+/// the parameters of a `go` command, times are in milliseconds
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct GoOptions {
+    pub(crate) depth: Option<usize>,
+    pub(crate) nodes: Option<u64>,
+    pub(crate) movetime: Option<u64>,
+    pub(crate) wtime: Option<u64>,
+    pub(crate) btime: Option<u64>,
+    pub(crate) winc: Option<u64>,
+    pub(crate) binc: Option<u64>,
+    pub(crate) movestogo: Option<u64>,
+    /// search until `stop`, even when the search itself is done
+    pub(crate) infinite: bool,
+}
+
+impl GoOptions {
+    /// unknown parameters like `searchmoves` and `ponder` are skipped
+    pub(crate) fn parse(tokens: &[&str]) -> Result<Self> {
+        let mut options = GoOptions::default();
+        let mut tokens = tokens.iter();
+        while let Some(&token) = tokens.next() {
+            let mut value = || -> Result<u64> {
+                let value = tokens
+                    .next()
+                    .ok_or_else(|| anyhow!("{token} needs a value"))?;
+                value
+                    .parse()
+                    .map_err(|_| anyhow!("invalid {token} value {value:?}"))
+            };
+            match token {
+                "depth" => options.depth = Some(value()? as usize),
+                "nodes" => options.nodes = Some(value()?),
+                "movetime" => options.movetime = Some(value()?),
+                "wtime" => options.wtime = Some(value()?),
+                "btime" => options.btime = Some(value()?),
+                "winc" => options.winc = Some(value()?),
+                "binc" => options.binc = Some(value()?),
+                "movestogo" => options.movestogo = Some(value()?),
+                "infinite" => options.infinite = true,
+                _ => {}
+            }
+        }
+        Ok(options)
+    }
+
+    /// a fixed move time wins over the clock, without either the search only stops on depth, nodes or `stop`
+    pub(crate) fn limits(&self, active_color: PieceColor) -> SearchLimits {
+        let mut limits = SearchLimits::default();
+        if let Some(depth) = self.depth {
+            limits = limits.with_depth(depth);
+        }
+        if let Some(nodes) = self.nodes {
+            limits = limits.with_nodes(nodes);
+        }
+        if self.infinite {
+            return limits;
+        }
+        if let Some(movetime) = self.movetime {
+            return limits.with_time(Duration::from_millis(movetime));
+        }
+        let (time, increment) = match active_color {
+            PieceColor::White => (self.wtime, self.winc),
+            PieceColor::Black => (self.btime, self.binc),
+        };
+        if let Some(time) = time {
+            let moves_to_go = self.movestogo.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+            let budget = time / moves_to_go + increment.unwrap_or(0) * 3 / 4;
+            let budget = budget.min(time.saturating_sub(MOVE_OVERHEAD_MS)).max(1);
+            limits = limits.with_time(Duration::from_millis(budget));
+        }
+        limits
+    }
+}
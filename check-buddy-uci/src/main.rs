@@ -0,0 +1,22 @@
+//! speaks the UCI protocol over stdin and stdout, so check buddy can be loaded
+//! into chess GUIs and tournament managers like cutechess-cli and Arena
+
+mod engine;
+mod go;
+
+use engine::Engine;
+use std::io::{self, BufRead};
+
+fn main() {
+    let mut engine = Engine::new();
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if !engine.handle(&line) {
+            break;
+        }
+    }
+    // the GUI is gone, a running search still reports its move
+    engine.stop();
+}
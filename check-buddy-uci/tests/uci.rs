@@ -0,0 +1,192 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+/// the engine binary, driven over stdin and stdout like a GUI would
+struct Engine {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Engine {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_check-buddy-uci"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("engine should start");
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Self {
+            child,
+            stdin,
+            stdout,
+        }
+    }
+
+    fn send(&mut self, command: &str) {
+        writeln!(self.stdin, "{command}").unwrap();
+    }
+
+    /// every line up to and including the first one starting with `prefix`
+    fn read_until(&mut self, prefix: &str) -> Vec<String> {
+        let mut lines = vec![];
+        loop {
+            let mut line = String::new();
+            assert_ne!(0, self.stdout.read_line(&mut line).unwrap(), "{lines:?}");
+            let line = line.trim_end().to_string();
+            let done = line.starts_with(prefix);
+            lines.push(line);
+            if done {
+                return lines;
+            }
+        }
+    }
+
+    fn best_move(&mut self, go: &str) -> (String, Vec<String>) {
+        self.send(go);
+        let mut lines = self.read_until("bestmove");
+        let best_move = lines.pop().unwrap().replace("bestmove ", "");
+        (best_move, lines)
+    }
+
+    fn quit(mut self) {
+        self.send("quit");
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+#[test]
+fn uci_should_identify_the_engine() {
+    let mut engine = Engine::start();
+    engine.send("uci");
+    let lines = engine.read_until("uciok");
+
+    assert!(lines[0].starts_with("id name check-buddy"));
+    assert!(lines.iter().any(|line| line.starts_with("id author")));
+    assert!(lines.contains(&"option name Hash type spin default 16 min 1 max 4096".to_string()));
+
+    engine.send("isready");
+    assert_eq!(vec!["readyok"], engine.read_until("readyok"));
+    engine.quit();
+}
+
+#[test]
+fn go_depth_should_report_every_iteration() {
+    let mut engine = Engine::start();
+    engine.send("ucinewgame");
+    engine.send("position startpos moves e2e4 e7e5 g1f3");
+    let (best_move, info) = engine.best_move("go depth 3");
+
+    assert_eq!(3, info.len(), "{info:?}");
+    for (depth, line) in (1..).zip(&info) {
+        assert!(
+            line.starts_with(&format!("info depth {depth} score cp ")),
+            "{line}"
+        );
+        assert!(
+            line.contains(" nodes ") && line.contains(" hashfull "),
+            "{line}"
+        );
+    }
+    // black to move after 1. e4 e5 2. Nf3
+    let pv = info[2].split(" pv ").nth(1).unwrap();
+    assert!(pv.starts_with(&best_move), "{pv}");
+    assert!(["7", "8"].iter().any(|rank| best_move[1..2] == **rank));
+    engine.quit();
+}
+
+#[test]
+fn go_should_report_mate_scores() {
+    let mut engine = Engine::start();
+    engine.send("position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+    let (best_move, info) = engine.best_move("go depth 3");
+
+    assert_eq!("a1a8", best_move);
+    assert!(info.last().unwrap().contains("score mate 1"), "{info:?}");
+
+    engine.send("position fen 6k1/5ppp/8/8/8/8/5PPP/r5K1 w - - 0 1");
+    let (best_move, _) = engine.best_move("go depth 3");
+    assert_eq!("0000", best_move);
+    engine.quit();
+}
+
+#[test]
+fn go_should_search_a_crowded_board() {
+    let mut engine = Engine::start();
+    engine.send("position fen QQQQQQQQ/Q6Q/Q6Q/Q6Q/Q6Q/QQ5Q/ppQ4Q/knQQQQQK w - - 0 1");
+    let (best_move, _) = engine.best_move("go depth 2");
+    assert_ne!("0000", best_move);
+    engine.quit();
+}
+
+#[test]
+fn position_should_apply_castling_and_promotion() {
+    let mut engine = Engine::start();
+    engine.send("position fen 4k3/P7/8/8/8/8/8/R3K2R w KQ - 0 1 moves e1c1 e8f7 a7a8n");
+    // the knight on a8 is white's, so black's king is the only black piece that can move
+    let (best_move, _) = engine.best_move("go depth 1");
+    assert!(best_move.starts_with("f7"), "{best_move}");
+
+    engine.send("position startpos moves e2e5");
+    assert_eq!(
+        vec!["info string illegal move e2e5"],
+        engine.read_until("info")
+    );
+    engine.quit();
+}
+
+#[test]
+fn chess960_should_castle_by_taking_the_rook() {
+    let mut engine = Engine::start();
+    engine.send("setoption name UCI_Chess960 value true");
+    let opening = "position startpos moves e2e4 e7e5 g1f3 b8c6 f1c4 g8f6";
+    engine.send(&format!("{opening} e1g1"));
+    assert_eq!(
+        vec!["info string illegal move e1g1"],
+        engine.read_until("info")
+    );
+
+    engine.send(&format!("{opening} e1h1"));
+    engine.send("isready");
+    assert_eq!(vec!["readyok"], engine.read_until("readyok"));
+
+    // only castling long brings the rook to d1, it's blocked by its own king otherwise
+    engine.send("position fen 2rkr3/2p1p3/8/8/8/8/8/RK6 w A - 0 1");
+    let (best_move, _) = engine.best_move("go depth 2");
+    assert_eq!("b1a1", best_move);
+    engine.quit();
+}
+
+#[test]
+fn stop_should_end_an_infinite_search() {
+    let mut engine = Engine::start();
+    engine.send("go infinite");
+    engine.send("stop");
+    let lines = engine.read_until("bestmove");
+
+    assert_ne!("bestmove 0000", lines.last().unwrap());
+    engine.quit();
+}
+
+#[test]
+fn clock_and_options_should_be_accepted() {
+    let mut engine = Engine::start();
+    engine.send("setoption name Hash value 1");
+    engine.send("setoption name Clear Hash");
+    engine.send("isready");
+    assert_eq!(vec!["readyok"], engine.read_until("readyok"));
+
+    engine.send("position startpos moves d2d4");
+    let (best_move, _) = engine.best_move("go wtime 1000 btime 1000 winc 10 binc 10");
+    assert_eq!(4, best_move.len());
+    let (best_move, _) = engine.best_move("go movetime 50");
+    assert_eq!(4, best_move.len());
+
+    engine.send("setoption name Hash value lots");
+    assert_eq!(
+        vec!["info string invalid Hash value \"lots\""],
+        engine.read_until("info")
+    );
+    engine.quit();
+}
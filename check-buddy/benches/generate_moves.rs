@@ -1,7 +1,10 @@
+use check_buddy::piece_color::PieceColor;
 use check_buddy::piece_type::PieceType;
 use check_buddy::BoardMap;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
 fn generate_moves(c: &mut Criterion) {
     let board = BoardMap::starting();
     let mut group = c.benchmark_group("generate_moves");
@@ -29,6 +32,37 @@ fn generate_moves(c: &mut Criterion) {
             })
         })
     });
+    group.bench_function("gen_all_legal_moves", |b| {
+        b.iter(|| black_box(board).gen_all_legal_moves().len())
+    });
+    let kiwipete = BoardMap::from_fen(KIWIPETE_FEN);
+    group.bench_function("gen_all_legal_moves_kiwipete", |b| {
+        b.iter(|| black_box(kiwipete).gen_all_legal_moves().len())
+    });
+    group.bench_function("legal_moves", |b| {
+        b.iter(|| black_box(board).legal_moves().len())
+    });
+    group.bench_function("legal_moves_kiwipete", |b| {
+        b.iter(|| black_box(kiwipete).legal_moves().len())
+    });
+    group.bench_function("active_pieces", |b| {
+        b.iter(|| black_box(kiwipete).get_active_pieces())
+    });
+    group.bench_function("pieces", |b| {
+        b.iter(|| black_box(kiwipete).pieces(PieceColor::White).count())
+    });
+    group.finish();
+}
+
+fn perft(c: &mut Criterion) {
+    let mut group = c.benchmark_group("perft");
+    group.sample_size(10);
+    for (name, board, depth) in [
+        ("starting_depth_4", BoardMap::starting(), 4),
+        ("kiwipete_depth_3", BoardMap::from_fen(KIWIPETE_FEN), 3),
+    ] {
+        group.bench_function(name, |b| b.iter(|| black_box(board).perft(depth)));
+    }
     group.finish();
 }
 
@@ -42,5 +76,5 @@ fn generate_piece_moves(c: &mut Criterion) {
         b.iter(|| board.gen_sliding(black_box([0, 1]), PieceType::Queen))
     });
 }
-criterion_group!(benches, generate_moves, generate_piece_moves);
+criterion_group!(benches, generate_moves, generate_piece_moves, perft);
 criterion_main!(benches);
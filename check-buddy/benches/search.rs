@@ -0,0 +1,24 @@
+use check_buddy::search_limits::SearchLimits;
+use check_buddy::{BoardMap, Searcher};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+fn search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search");
+    group.sample_size(10);
+    for (name, board) in [
+        ("starting_depth_4", BoardMap::starting()),
+        ("kiwipete_depth_4", BoardMap::from_fen(KIWIPETE_FEN)),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                Searcher::new().best_move(black_box(&board), SearchLimits::default().with_depth(4))
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, search);
+criterion_main!(benches);
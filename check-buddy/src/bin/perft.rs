@@ -0,0 +1,46 @@
+//! prints the perft divide of a position in the same format as other engines,
+//! so the counts can be diffed move by move
+//!
+//! usage: `perft <depth> [fen]`, the starting position when no FEN is given.
+//! positions are cached in a transposition table of `PERFT_HASH_MB` megabytes (64 by default)
+
+use check_buddy::{BoardMap, TranspositionTable};
+use std::env;
+use std::process::exit;
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let Some(depth) = args.first().and_then(|depth| depth.parse::<usize>().ok()) else {
+        eprintln!("usage: perft <depth> [fen]");
+        exit(1);
+    };
+    let mut board = if args.len() > 1 {
+        match BoardMap::try_from_fen(&args[1..].join(" ")) {
+            Ok(board) => board,
+            Err(e) => {
+                eprintln!("invalid FEN: {e}");
+                exit(1);
+            }
+        }
+    } else {
+        BoardMap::starting()
+    };
+
+    let size_mb = env::var("PERFT_HASH_MB")
+        .ok()
+        .and_then(|size| size.parse().ok())
+        .unwrap_or(64);
+    let mut table = TranspositionTable::new(size_mb);
+    let mut total = 0;
+    if depth > 0 {
+        for position_move in board.gen_all_legal_moves() {
+            let undo = board.make_move(position_move);
+            let nodes = board.perft_with_table(depth - 1, &mut table);
+            board.unmake_move(undo);
+            println!("{position_move}: {nodes}");
+            total += nodes;
+        }
+    }
+    println!();
+    println!("Nodes searched: {total}");
+}
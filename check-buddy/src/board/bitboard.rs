@@ -0,0 +1,239 @@
+//! bit `row * 8 + file` of a bitboard stands for the square `[row, file]`,
+//! so `a8` is the lowest bit and `h1` the highest
+
+use crate::moves::position_move::Position;
+use crate::piece_color::PieceColor;
+
+pub type Bitboard = u64;
+
+pub const EMPTY: Bitboard = 0;
+/// squares like `a8` and `h1`, where `(row + file)` is even
+pub const LIGHT_SQUARES: Bitboard = 0x55aa_55aa_55aa_55aa;
+pub const DARK_SQUARES: Bitboard = !LIGHT_SQUARES;
+
+pub const fn square_index(position: Position) -> usize {
+    position[0] * 8 + position[1]
+}
+
+pub const fn square_bit(position: Position) -> Bitboard {
+    1 << square_index(position)
+}
+
+pub const fn index_position(index: usize) -> Position {
+    [index / 8, index % 8]
+}
+
+/// iterates the positions of all set bits, lowest bit first
+#[derive(Clone, Copy, Debug)]
+pub struct Positions(Bitboard);
+
+impl Iterator for Positions {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == EMPTY {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(index_position(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.0.count_ones() as usize;
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for Positions {}
+
+pub fn positions(bitboard: Bitboard) -> Positions {
+    Positions(bitboard)
+}
+
+pub fn knight_attacks(position: Position) -> Bitboard {
+    KNIGHT_ATTACKS[square_index(position)]
+}
+
+pub fn king_attacks(position: Position) -> Bitboard {
+    KING_ATTACKS[square_index(position)]
+}
+
+/// squares a pawn of `color` standing on `position` can capture on
+pub fn pawn_attacks(position: Position, color: PieceColor) -> Bitboard {
+    PAWN_ATTACKS[color_index(color)][square_index(position)]
+}
+
+pub fn rook_attacks(position: Position, occupied: Bitboard) -> Bitboard {
+    ORTHOGONAL.iter().fold(EMPTY, |attacks, &d| {
+        attacks | ray_attacks(position, d, occupied)
+    })
+}
+
+pub fn bishop_attacks(position: Position, occupied: Bitboard) -> Bitboard {
+    DIAGONAL.iter().fold(EMPTY, |attacks, &d| {
+        attacks | ray_attacks(position, d, occupied)
+    })
+}
+
+pub fn queen_attacks(position: Position, occupied: Bitboard) -> Bitboard {
+    rook_attacks(position, occupied) | bishop_attacks(position, occupied)
+}
+
+/// squares strictly between two positions on a shared line, empty if they don't share one
+pub fn between(from: Position, to: Position) -> Bitboard {
+    BETWEEN[square_index(from)][square_index(to)]
+}
+
+/// every square of the line through both positions from edge to edge, the two included,
+/// empty if they don't share one
+pub fn line(a: Position, b: Position) -> Bitboard {
+    LINE[square_index(a)][square_index(b)]
+}
+
+pub(crate) const fn color_index(color: PieceColor) -> usize {
+    match color {
+        PieceColor::White => 0,
+        PieceColor::Black => 1,
+    }
+}
+
+/// the ray stops at, and includes, the first occupied square
+fn ray_attacks(position: Position, direction: usize, occupied: Bitboard) -> Bitboard {
+    let ray = RAYS[direction][square_index(position)];
+    let blockers = ray & occupied;
+    if blockers == EMPTY {
+        return ray;
+    }
+    let blocker = if DIRECTIONS[direction][0] * 8 + DIRECTIONS[direction][1] > 0 {
+        blockers.trailing_zeros() as usize
+    } else {
+        63 - blockers.leading_zeros() as usize
+    };
+    ray ^ RAYS[direction][blocker]
+}
+
+/// `[row, file]` steps, orthogonal directions first
+const DIRECTIONS: [[i32; 2]; 8] = [
+    [1, 0],
+    [0, 1],
+    [-1, 0],
+    [0, -1],
+    [1, 1],
+    [1, -1],
+    [-1, -1],
+    [-1, 1],
+];
+const ORTHOGONAL: [usize; 4] = [0, 1, 2, 3];
+const DIAGONAL: [usize; 4] = [4, 5, 6, 7];
+
+const fn on_board(row: i32, file: i32) -> bool {
+    row >= 0 && row < 8 && file >= 0 && file < 8
+}
+
+const fn step_table(steps: &[[i32; 2]]) -> [Bitboard; 64] {
+    let mut table = [EMPTY; 64];
+    let mut index = 0;
+    while index < 64 {
+        let (row, file) = ((index / 8) as i32, (index % 8) as i32);
+        let mut s = 0;
+        while s < steps.len() {
+            let (r, f) = (row + steps[s][0], file + steps[s][1]);
+            if on_board(r, f) {
+                table[index] |= 1 << (r * 8 + f);
+            }
+            s += 1;
+        }
+        index += 1;
+    }
+    table
+}
+
+const fn ray_table() -> [[Bitboard; 64]; 8] {
+    let mut table = [[EMPTY; 64]; 8];
+    let mut direction = 0;
+    while direction < 8 {
+        let mut index = 0;
+        while index < 64 {
+            let (mut row, mut file) = ((index / 8) as i32, (index % 8) as i32);
+            loop {
+                row += DIRECTIONS[direction][0];
+                file += DIRECTIONS[direction][1];
+                if !on_board(row, file) {
+                    break;
+                }
+                table[direction][index] |= 1 << (row * 8 + file);
+            }
+            index += 1;
+        }
+        direction += 1;
+    }
+    table
+}
+
+const fn between_table() -> [[Bitboard; 64]; 64] {
+    let mut table = [[EMPTY; 64]; 64];
+    let mut from = 0;
+    while from < 64 {
+        let mut direction = 0;
+        while direction < 8 {
+            let (mut row, mut file) = ((from / 8) as i32, (from % 8) as i32);
+            let mut squares = EMPTY;
+            loop {
+                row += DIRECTIONS[direction][0];
+                file += DIRECTIONS[direction][1];
+                if !on_board(row, file) {
+                    break;
+                }
+                let to = (row * 8 + file) as usize;
+                table[from][to] = squares;
+                squares |= 1 << to;
+            }
+            direction += 1;
+        }
+        from += 1;
+    }
+    table
+}
+
+const fn line_table() -> [[Bitboard; 64]; 64] {
+    let rays = ray_table();
+    let mut table = [[EMPTY; 64]; 64];
+    let mut from = 0;
+    while from < 64 {
+        let mut direction = 0;
+        while direction < 8 {
+            // the opposite direction is two further along within the orthogonal or diagonal four
+            let opposite = direction / 4 * 4 + (direction + 2) % 4;
+            let line = rays[direction][from] | rays[opposite][from] | 1 << from;
+            let mut ray = rays[direction][from];
+            while ray != EMPTY {
+                table[from][ray.trailing_zeros() as usize] = line;
+                ray &= ray - 1;
+            }
+            direction += 1;
+        }
+        from += 1;
+    }
+    table
+}
+
+static KNIGHT_ATTACKS: [Bitboard; 64] = step_table(&[
+    [1, -2],
+    [2, -1],
+    [2, 1],
+    [1, 2],
+    [-1, 2],
+    [-2, 1],
+    [-2, -1],
+    [-1, -2],
+]);
+static KING_ATTACKS: [Bitboard; 64] = step_table(&DIRECTIONS);
+/// white pawns move towards row 0, black pawns towards row 7
+static PAWN_ATTACKS: [[Bitboard; 64]; 2] = [
+    step_table(&[[-1, -1], [-1, 1]]),
+    step_table(&[[1, -1], [1, 1]]),
+];
+static RAYS: [[Bitboard; 64]; 8] = ray_table();
+static BETWEEN: [[Bitboard; 64]; 64] = between_table();
+static LINE: [[Bitboard; 64]; 64] = line_table();
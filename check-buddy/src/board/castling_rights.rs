@@ -0,0 +1,124 @@
+use super::bitboard::color_index;
+use crate::piece_color::PieceColor;
+use std::fmt::{Display, Formatter};
+
+/// castling availability as written in the third FEN field (`KQkq`).
+/// Rights castling with a rook off the a and h files are written
+/// with the rook's file instead, like Shredder-FEN does (`HAha`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CastlingRights {
+    pub white_short: bool,
+    pub white_long: bool,
+    pub black_short: bool,
+    pub black_long: bool,
+    /// file of the rook each right castles with, short then long, white first.
+    /// The h and a files in standard chess, anywhere in Chess960
+    pub rook_files: [[usize; 2]; 2],
+}
+
+impl Default for CastlingRights {
+    fn default() -> Self {
+        Self {
+            white_short: false,
+            white_long: false,
+            black_short: false,
+            black_long: false,
+            rook_files: [[7, 0]; 2],
+        }
+    }
+}
+
+impl CastlingRights {
+    pub fn all() -> Self {
+        Self {
+            white_short: true,
+            white_long: true,
+            black_short: true,
+            black_long: true,
+            ..Default::default()
+        }
+    }
+    pub fn none() -> Self {
+        Self::default()
+    }
+    pub fn can_short_castle(&self, color: PieceColor) -> bool {
+        match color {
+            PieceColor::White => self.white_short,
+            PieceColor::Black => self.black_short,
+        }
+    }
+    pub fn can_long_castle(&self, color: PieceColor) -> bool {
+        match color {
+            PieceColor::White => self.white_long,
+            PieceColor::Black => self.black_long,
+        }
+    }
+    /// file of the rook castling short or long
+    pub fn rook_file(&self, color: PieceColor, short: bool) -> usize {
+        self.rook_files[color_index(color)][usize::from(!short)]
+    }
+    /// removes both rights of a color, e.g. after its king moved
+    pub fn remove(&mut self, color: PieceColor) {
+        match color {
+            PieceColor::White => {
+                self.white_short = false;
+                self.white_long = false;
+            }
+            PieceColor::Black => {
+                self.black_short = false;
+                self.black_long = false;
+            }
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        !(self.white_short || self.white_long || self.black_short || self.black_long)
+    }
+    /// every right written with its rook's file, like `HAha`
+    pub fn to_shredder_fen(&self) -> String {
+        if self.is_empty() {
+            return "-".to_string();
+        }
+        self.enabled()
+            .map(|(color, short, _)| file_letter(color, self.rook_file(color, short)))
+            .collect()
+    }
+
+    /// the color, side and standard letter of every right that's still there
+    fn enabled(&self) -> impl Iterator<Item = (PieceColor, bool, char)> {
+        [
+            (self.white_short, PieceColor::White, true, 'K'),
+            (self.white_long, PieceColor::White, false, 'Q'),
+            (self.black_short, PieceColor::Black, true, 'k'),
+            (self.black_long, PieceColor::Black, false, 'q'),
+        ]
+        .into_iter()
+        .filter(|(enabled, ..)| *enabled)
+        .map(|(_, color, short, letter)| (color, short, letter))
+    }
+}
+
+/// the file in uppercase for white and lowercase for black
+fn file_letter(color: PieceColor, file: usize) -> char {
+    let letter = (b'a' + file as u8) as char;
+    match color {
+        PieceColor::White => letter.to_ascii_uppercase(),
+        PieceColor::Black => letter,
+    }
+}
+
+impl Display for CastlingRights {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "-");
+        }
+        for (color, short, letter) in self.enabled() {
+            let file = self.rook_file(color, short);
+            if file == if short { 7 } else { 0 } {
+                write!(f, "{letter}")?;
+            } else {
+                write!(f, "{}", file_letter(color, file))?;
+            }
+        }
+        Ok(())
+    }
+}
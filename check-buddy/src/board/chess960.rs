@@ -0,0 +1,63 @@
+use super::BoardMap;
+
+/// knight files among the five squares left after placing the bishops and queen
+const KNIGHTS: [(usize, usize); 10] = [
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (2, 3),
+    (2, 4),
+    (3, 4),
+];
+
+impl BoardMap {
+    /// one of the 960 Chess960 starting positions, numbered the standard way from 0 to 959.
+    /// Number 518 is the standard starting position. Castling moves are written
+    /// as the king taking its own rook
+    pub fn chess960_starting(index: usize) -> Option<Self> {
+        if index >= 960 {
+            return None;
+        }
+        let mut back_rank = [None; 8];
+        let mut n = index;
+        // bishops on opposite colors, the light squared one on b, d, f or h
+        back_rank[(n % 4) * 2 + 1] = Some('b');
+        n /= 4;
+        back_rank[(n % 4) * 2] = Some('b');
+        n /= 4;
+        place(&mut back_rank, n % 6, 'q');
+        n /= 6;
+        let (first, second) = KNIGHTS[n];
+        // the second knight skips the square the first one took
+        place(&mut back_rank, first, 'n');
+        place(&mut back_rank, second - 1, 'n');
+        // the king always ends up between the rooks
+        for piece in ['r', 'k', 'r'] {
+            place(&mut back_rank, 0, piece);
+        }
+
+        let black = back_rank.iter().flatten().collect::<String>();
+        let fen = format!(
+            "{black}/pppppppp/8/8/8/8/PPPPPPPP/{} w KQkq - 0 1",
+            black.to_ascii_uppercase()
+        );
+        let mut board = BoardMap::from_fen(fen);
+        board.set_chess960(true);
+        Some(board)
+    }
+}
+
+/// puts the piece on the `nth` empty square
+fn place(back_rank: &mut [Option<char>; 8], nth: usize, piece: char) {
+    if let Some(square) = back_rank
+        .iter_mut()
+        .filter(|square| square.is_none())
+        .nth(nth)
+    {
+        *square = Some(piece);
+    }
+}
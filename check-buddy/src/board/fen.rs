@@ -0,0 +1,394 @@
+use super::bitboard::color_index;
+use super::BoardMap;
+use crate::errors::{FenError, FenField};
+use crate::moves::position_move::{notation_to_position, position_to_notation};
+use crate::piece::piece_type::*;
+use crate::piece_color::PieceColor;
+use std::str::FromStr;
+
+impl BoardMap {
+    /// reads all six FEN fields, missing trailing fields fall back to `w - - 0 1`
+    ///
+    /// # Panics
+    /// when the FEN can't be parsed. Parsable but illegal positions are accepted,
+    /// use [`BoardMap::try_from_fen`] for input that isn't trusted.
+    pub fn from_fen(fen: impl Into<String>) -> Self {
+        let fen = fen.into();
+        Self::parse_fen(&fen).unwrap_or_else(|e| panic!("FEN incorrect: {e}"))
+    }
+    /// reads and validates all six FEN fields, missing trailing fields fall back to `w - - 0 1`
+    pub fn try_from_fen(fen: &str) -> Result<Self, FenError> {
+        let board = Self::parse_fen(fen)?;
+        board.validate()?;
+        Ok(board)
+    }
+    /// checks if the position could occur in a game
+    pub fn validate(&self) -> Result<(), FenError> {
+        for color in [PieceColor::White, PieceColor::Black] {
+            match self.find_piece(color, PieceType::King).len() {
+                0 => return Err(FenError::MissingKing(color)),
+                1 => {}
+                _ => return Err(FenError::TooManyKings(color)),
+            }
+        }
+
+        for row in [0, 7] {
+            for (file, piece) in self.squares[row].iter().enumerate() {
+                if let Some(PieceType::Pawn(_)) = piece.get_type() {
+                    return Err(FenError::PawnOnBackRank {
+                        rank: 8 - row,
+                        file: (b'a' + file as u8) as char,
+                    });
+                }
+            }
+        }
+
+        for (right, enabled, color, short) in [
+            (
+                'K',
+                self.castling_rights.white_short,
+                PieceColor::White,
+                true,
+            ),
+            (
+                'Q',
+                self.castling_rights.white_long,
+                PieceColor::White,
+                false,
+            ),
+            (
+                'k',
+                self.castling_rights.black_short,
+                PieceColor::Black,
+                true,
+            ),
+            (
+                'q',
+                self.castling_rights.black_long,
+                PieceColor::Black,
+                false,
+            ),
+        ] {
+            let row = super::home_row(color);
+            let rook_file = self.castling_rights.rook_file(color, short);
+            let rook = self.squares[row][rook_file];
+            // the rook has to be on the side of the king it castles to
+            let king_home = self
+                .home_king_file(color)
+                .is_some_and(|king_file| (rook_file > king_file) == short);
+            if enabled
+                && !(king_home
+                    && rook.get_type() == Some(PieceType::Rook)
+                    && rook.get_color() == color)
+            {
+                return Err(FenError::CastlingWithoutPieces(right));
+            }
+        }
+
+        if let Some(target) = self.en_passant {
+            // the opponent just made a double step over the target square
+            let (target_row, pawn_row, start_row) = match self.active_color {
+                PieceColor::White => (2, 3, 1),
+                PieceColor::Black => (5, 4, 6),
+            };
+            let pawn = self.squares[pawn_row][target[1]];
+            if target[0] != target_row
+                || !matches!(pawn.get_type(), Some(PieceType::Pawn(_)))
+                || pawn.get_color() == self.active_color
+                || self.squares[target_row][target[1]].is_piece()
+                || self.squares[start_row][target[1]].is_piece()
+            {
+                return Err(FenError::InvalidValue {
+                    field: FenField::EnPassant,
+                    value: position_to_notation(target),
+                });
+            }
+        }
+
+        let opponent_color = self.active_color.opposite();
+        let opponent_king = self.find_piece(opponent_color, PieceType::King)[0];
+        if self.is_square_attacked(opponent_king, self.active_color) {
+            return Err(FenError::KingCapturable(opponent_color));
+        }
+
+        Ok(())
+    }
+    /// writes all six FEN fields, the castling rights of a Chess960 board as Shredder-FEN
+    pub fn get_fen(&self) -> String {
+        let mut fen = String::new();
+        let squares = self.squares;
+        for row in squares {
+            let mut space = 0;
+            for col in row {
+                if let Some(piece_type) = col.get_type() {
+                    if space != 0 {
+                        fen.push_str(space.to_string().as_str());
+                        space = 0;
+                    }
+
+                    let mut piece_character = match piece_type {
+                        PieceType::Rook => 'r',
+                        PieceType::Pawn(_) => 'p',
+                        PieceType::King => 'k',
+                        PieceType::Queen => 'q',
+                        PieceType::Bishop => 'b',
+                        PieceType::Knight => 'n',
+                    };
+
+                    if col.get_color() == PieceColor::White {
+                        piece_character = piece_character.to_ascii_uppercase();
+                    }
+
+                    fen.push(piece_character);
+                } else {
+                    space += 1;
+                }
+            }
+            if space != 0 {
+                fen.push_str(space.to_string().as_str());
+            }
+            fen.push('/');
+        }
+
+        fen.pop();
+
+        let active_color = match self.active_color {
+            PieceColor::White => 'w',
+            PieceColor::Black => 'b',
+        };
+        let en_passant = self
+            .en_passant
+            .map(position_to_notation)
+            .unwrap_or_else(|| "-".to_string());
+        let castling_rights = if self.chess960 {
+            self.castling_rights.to_shredder_fen()
+        } else {
+            self.castling_rights.to_string()
+        };
+        fen.push_str(&format!(
+            " {} {} {} {} {}",
+            active_color, castling_rights, en_passant, self.halfmove_clock, self.fullmove_number
+        ));
+        fen
+    }
+
+    fn parse_fen(fen: &str) -> Result<Self, FenError> {
+        let mut board = Self::default();
+        let sections = fen.split_whitespace().collect::<Vec<_>>();
+        if sections.len() > 6 {
+            return Err(FenError::TooManyFields);
+        }
+        let placement = sections.first().ok_or(FenError::Empty)?;
+        board.parse_placement(placement)?;
+
+        if let Some(active_color) = sections.get(1) {
+            board.active_color = match *active_color {
+                "w" => PieceColor::White,
+                "b" => PieceColor::Black,
+                _ => {
+                    return Err(FenError::InvalidValue {
+                        field: FenField::ActiveColor,
+                        value: active_color.to_string(),
+                    })
+                }
+            };
+        }
+
+        if let Some(castling) = sections.get(2).filter(|&&s| s != "-") {
+            for c in castling.chars() {
+                board.parse_castling_right(c)?;
+            }
+            // rook files and rights castling with a king or rook off its standard square
+            // only show up in Chess960
+            let rights = board.castling_rights;
+            let shredder = castling.chars().any(|c| !"KQkq".contains(c));
+            board.chess960 = shredder
+                || [
+                    (rights.white_short, PieceColor::White, true),
+                    (rights.white_long, PieceColor::White, false),
+                    (rights.black_short, PieceColor::Black, true),
+                    (rights.black_long, PieceColor::Black, false),
+                ]
+                .into_iter()
+                .any(|(enabled, color, short)| {
+                    enabled
+                        && (board.home_king_file(color) != Some(4)
+                            || rights.rook_file(color, short) != if short { 7 } else { 0 })
+                });
+        }
+
+        if let Some(en_passant) = sections.get(3).filter(|&&s| s != "-") {
+            let target = notation_to_position(en_passant)
+                .filter(|target| target[0] == 2 || target[0] == 5)
+                .ok_or_else(|| FenError::InvalidValue {
+                    field: FenField::EnPassant,
+                    value: en_passant.to_string(),
+                })?;
+            board.en_passant = Some(target);
+            // the pawn that just made the double step sits one square past the target
+            let pawn_position = if target[0] == 2 {
+                [3, target[1]]
+            } else {
+                [4, target[1]]
+            };
+            let pawn = board.square_mut(pawn_position);
+            if let Some(PieceType::Pawn(false)) = pawn.get_type() {
+                pawn.0 += 32;
+            }
+        }
+
+        if let Some(halfmove_clock) = sections.get(4) {
+            board.halfmove_clock = halfmove_clock.parse().map_err(|_| FenError::InvalidValue {
+                field: FenField::HalfmoveClock,
+                value: halfmove_clock.to_string(),
+            })?;
+        }
+        if let Some(fullmove_number) = sections.get(5) {
+            board.fullmove_number =
+                fullmove_number
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| FenError::InvalidValue {
+                        field: FenField::FullmoveNumber,
+                        value: fullmove_number.to_string(),
+                    })?;
+        }
+
+        board.zobrist = board.compute_zobrist();
+        Ok(board)
+    }
+
+    /// `KQkq` castle with the outermost rook on that side of the king, like X-FEN does.
+    /// A file letter like `H` or `a` names the rook, like Shredder-FEN does
+    fn parse_castling_right(&mut self, c: char) -> Result<(), FenError> {
+        let invalid = || FenError::InvalidCharacter {
+            field: FenField::Castling,
+            character: c,
+        };
+        let color = if c.is_ascii_uppercase() {
+            PieceColor::White
+        } else {
+            PieceColor::Black
+        };
+        let row = super::home_row(color);
+        let king_file = self.home_king_file(color);
+        let is_rook = |file: &usize| {
+            let piece = self.squares[row][*file];
+            piece.get_type() == Some(PieceType::Rook) && piece.get_color() == color
+        };
+        let (short, rook_file) = match c.to_ascii_lowercase() {
+            // without a king the defaults are kept, so validating reports the missing pieces
+            'k' => (
+                true,
+                king_file
+                    .and_then(|king_file| (king_file + 1..8).rev().find(is_rook))
+                    .unwrap_or(7),
+            ),
+            'q' => (
+                false,
+                king_file
+                    .and_then(|king_file| (0..king_file).find(is_rook))
+                    .unwrap_or(0),
+            ),
+            'a'..='h' => {
+                let file = (c.to_ascii_lowercase() as u8 - b'a') as usize;
+                let king_file = king_file.ok_or_else(invalid)?;
+                if file == king_file {
+                    return Err(invalid());
+                }
+                (file > king_file, file)
+            }
+            _ => return Err(invalid()),
+        };
+        let right = match (color, short) {
+            (PieceColor::White, true) => &mut self.castling_rights.white_short,
+            (PieceColor::White, false) => &mut self.castling_rights.white_long,
+            (PieceColor::Black, true) => &mut self.castling_rights.black_short,
+            (PieceColor::Black, false) => &mut self.castling_rights.black_long,
+        };
+        // every right can only be given once
+        if *right {
+            return Err(invalid());
+        }
+        *right = true;
+        self.castling_rights.rook_files[color_index(color)][usize::from(!short)] = rook_file;
+        Ok(())
+    }
+
+    /// file of the color's king when it stands on its back rank
+    fn home_king_file(&self, color: PieceColor) -> Option<usize> {
+        let row = super::home_row(color);
+        self.find_piece(color, PieceType::King)
+            .into_iter()
+            .find(|king| king[0] == row)
+            .map(|king| king[1])
+    }
+
+    fn parse_placement(&mut self, placement: &str) -> Result<(), FenError> {
+        let ranks = placement.split('/').collect::<Vec<_>>();
+        if ranks.len() != 8 {
+            return Err(FenError::InvalidRankCount(ranks.len()));
+        }
+
+        for (row, pieces) in ranks.iter().enumerate() {
+            let rank = 8 - row;
+            let mut file = 0;
+            for character in pieces.chars() {
+                if let Some(space) = character.to_digit(10).filter(|d| (1..=8).contains(d)) {
+                    file += space as usize;
+                    continue;
+                }
+
+                let piece_type = match character.to_ascii_lowercase() {
+                    'p' => PAWN,
+                    'r' => ROOK,
+                    'b' => BISHOP,
+                    'q' => QUEEN,
+                    'k' => KING,
+                    'n' => KNIGHT,
+                    _ => return Err(FenError::InvalidPiece { rank, character }),
+                };
+                let color = if character.is_ascii_uppercase() {
+                    WHITE
+                } else {
+                    BLACK
+                };
+                if file < 8 {
+                    self.set_piece([row, file], color | piece_type);
+                }
+                file += 1;
+            }
+            if file != 8 {
+                return Err(FenError::InvalidRankLength { rank, length: file });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for BoardMap {
+    type Err = FenError;
+
+    fn from_str(fen: &str) -> Result<Self, Self::Err> {
+        Self::try_from_fen(fen)
+    }
+}
+
+/// written as its FEN, so positions stay readable in JSON
+#[cfg(feature = "serde")]
+impl serde::Serialize for BoardMap {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.get_fen())
+    }
+}
+
+/// read from a FEN like [`BoardMap::from_fen`], so every board written reads back the same.
+/// Only FENs that can't be parsed are refused, use [`BoardMap::validate`] for legality
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BoardMap {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fen = String::deserialize(deserializer)?;
+        Self::parse_fen(&fen).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,104 @@
+use super::bitboard::*;
+use super::{piece_index, BoardMap};
+use crate::moves::position_move::Position;
+use crate::piece::piece_type::*;
+use crate::piece_color::PieceColor;
+
+/// what a king is up against, worked out once per position so every move
+/// can be checked against a few masks instead of being played out
+#[derive(Clone, Copy, Debug)]
+pub(super) struct KingSafety {
+    king: Option<Position>,
+    /// pieces giving check
+    checkers: Bitboard,
+    /// squares any piece but the king has to move to: anywhere when not in check,
+    /// onto the checker or in between in single check and nowhere in double check
+    evasions: Bitboard,
+    /// own pieces that are all that stands between the king and a slider
+    pinned: Bitboard,
+}
+
+impl BoardMap {
+    pub(super) fn king_safety(&self, color: PieceColor) -> KingSafety {
+        let Some(king) = positions(self.get_bitboard(color, PieceType::King)).next() else {
+            return KingSafety {
+                king: None,
+                checkers: EMPTY,
+                evasions: !EMPTY,
+                pinned: EMPTY,
+            };
+        };
+        let occupied = self.get_occupied();
+        let checkers = self.attackers(king, color.opposite(), occupied);
+        let evasions = match checkers.count_ones() {
+            0 => !EMPTY,
+            1 => checkers | between(king, index_position(checkers.trailing_zeros() as usize)),
+            _ => EMPTY,
+        };
+
+        // sliders that would see the king on an empty board pin a lone own piece in the way
+        let piece_bitboard = |value: u32| self.pieces[piece_index(value)];
+        let diagonal = piece_bitboard(BISHOP) | piece_bitboard(QUEEN);
+        let orthogonal = piece_bitboard(ROOK) | piece_bitboard(QUEEN);
+        let snipers = ((bishop_attacks(king, EMPTY) & diagonal)
+            | (rook_attacks(king, EMPTY) & orthogonal))
+            & self.get_color_bitboard(color.opposite());
+        let mut pinned = EMPTY;
+        for sniper in positions(snipers) {
+            let blockers = between(king, sniper) & occupied;
+            if blockers.count_ones() == 1 {
+                pinned |= blockers & self.get_color_bitboard(color);
+            }
+        }
+
+        KingSafety {
+            king: Some(king),
+            checkers,
+            evasions,
+            pinned,
+        }
+    }
+
+    /// squares the piece on `from` can legally move to, castling included
+    pub(super) fn legal_targets(&self, from: Position, safety: &KingSafety) -> Bitboard {
+        if self.get_piece(from).get_type() == Some(PieceType::King) {
+            return self.legal_king_targets(from);
+        }
+        if safety.checkers.count_ones() > 1 {
+            return EMPTY;
+        }
+
+        let mut targets = self.gen_targets(from);
+        let en_passant = self.en_passant.filter(|&target| {
+            targets & square_bit(target) != EMPTY && self.is_en_passant(from, target)
+        });
+        if let Some(en_passant) = en_passant {
+            targets &= !square_bit(en_passant);
+        }
+        targets &= safety.evasions;
+        if let Some(king) = safety
+            .king
+            .filter(|_| safety.pinned & square_bit(from) != EMPTY)
+        {
+            targets &= line(king, from);
+        }
+        // en passant takes a piece off a square it doesn't land on, which can uncover
+        // the king along the row both pawns stood on, so it's played out on the bitboards
+        if let Some(en_passant) = en_passant.filter(|&to| !self.leaves_king_in_check(from, to)) {
+            targets |= square_bit(en_passant);
+        }
+        targets
+    }
+
+    /// the king can't step onto an attacked square, looked at without the king in the way
+    /// so it can't retreat along the line of a slider. Castling is checked in full already
+    fn legal_king_targets(&self, from: Position) -> Bitboard {
+        let opponent = self.get_piece(from).get_color().opposite();
+        let occupied = self.get_occupied() & !square_bit(from);
+        positions(king_attacks(from) & !self.own_pieces(from))
+            .filter(|&to| self.attackers(to, opponent, occupied) == EMPTY)
+            .fold(self.castling_targets(from), |targets, to| {
+                targets | square_bit(to)
+            })
+    }
+}
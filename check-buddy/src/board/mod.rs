@@ -0,0 +1,907 @@
+use crate::errors::*;
+use crate::moves::chess_move::{CASTLE, DOUBLE_PUSH, EN_PASSANT};
+use crate::moves::move_list::MoveList;
+use crate::moves::position_move::{Position, PositionMove, PROMOTION_PIECES};
+use crate::outcome::Outcome;
+use crate::piece::{piece_type::*, Piece};
+use crate::piece_color::PieceColor;
+use crate::uci_move::UciMove;
+use anyhow::{anyhow, Result};
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+pub mod bitboard;
+pub mod castling_rights;
+mod chess960;
+mod fen;
+mod legal;
+mod perft;
+mod piece_mut;
+mod san;
+mod undo;
+mod zobrist;
+
+use bitboard::*;
+use castling_rights::CastlingRights;
+pub use piece_mut::{PieceMut, SquaresMut};
+pub use undo::Undo;
+
+#[derive(Clone, Copy)]
+pub struct BoardMap {
+    squares: [[Piece; 8]; 8],
+    /// one bitboard per piece type, indexed by the piece value minus one
+    pieces: [Bitboard; 6],
+    /// one bitboard per color, white first
+    colors: [Bitboard; 2],
+    active_color: PieceColor,
+    castling_rights: CastlingRights,
+    en_passant: Option<Position>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    /// kept up to date by every change to the position
+    zobrist: u64,
+    /// castling is written as the king taking its own rook, like `e1h1`
+    chess960: bool,
+}
+
+impl Default for BoardMap {
+    fn default() -> Self {
+        let squares = [[Piece(0); 8]; 8];
+
+        Self {
+            squares,
+            pieces: [EMPTY; 6],
+            colors: [EMPTY; 2],
+            active_color: PieceColor::White,
+            castling_rights: CastlingRights::none(),
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            zobrist: 0,
+            chess960: false,
+        }
+    }
+}
+
+impl BoardMap {
+    pub fn empty() -> Self {
+        BoardMap::default()
+    }
+    /// starting position: rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1
+    pub fn starting() -> Self {
+        BoardMap::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
+    }
+    pub fn get_piece(&self, pos: Position) -> Piece {
+        self.squares[pos[0]][pos[1]]
+    }
+    pub fn find_piece(&self, piece_color: PieceColor, piece_type: PieceType) -> Vec<Position> {
+        positions(self.get_bitboard(piece_color, piece_type)).collect()
+    }
+    /// squares holding a piece of the given color and type
+    pub fn get_bitboard(&self, piece_color: PieceColor, piece_type: PieceType) -> Bitboard {
+        self.pieces[piece_index(piece_type.to_value())] & self.colors[color_index(piece_color)]
+    }
+    /// squares holding a piece of the given color
+    pub fn get_color_bitboard(&self, piece_color: PieceColor) -> Bitboard {
+        self.colors[color_index(piece_color)]
+    }
+    /// squares holding any piece
+    pub fn get_occupied(&self) -> Bitboard {
+        self.colors[0] | self.colors[1]
+    }
+    /// the piece on a square to change in place, e.g. `board.get_piece_mut(pos).0 = ROOK | WHITE`.
+    /// The change reaches the bitboards once the guard is dropped
+    pub fn get_piece_mut(&mut self, pos: Position) -> PieceMut<'_> {
+        PieceMut::new(self, pos)
+    }
+    /// every square to change at once, like `board.squares_mut()[7][4] = Piece(KING | WHITE)`.
+    /// The bitboards are built again from the squares once the guard is dropped
+    pub fn squares_mut(&mut self) -> SquaresMut<'_> {
+        SquaresMut::new(self)
+    }
+    pub fn get_active_color(&self) -> &PieceColor {
+        &self.active_color
+    }
+    pub fn get_castling_rights(&self) -> &CastlingRights {
+        &self.castling_rights
+    }
+    /// the square behind a pawn that just made a double step
+    pub fn get_en_passant(&self) -> Option<Position> {
+        self.en_passant
+    }
+    /// number of halfmoves since the last capture or pawn move
+    pub fn get_halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+    pub fn get_fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+    /// hash of the pieces, side to move, castling rights and en passant file,
+    /// the same for every transposition into the position
+    pub fn zobrist(&self) -> u64 {
+        self.zobrist
+    }
+    /// whether castling moves are written as the king taking its own rook
+    pub fn is_chess960(&self) -> bool {
+        self.chess960
+    }
+    /// switches how castling moves are written, standard positions can be played either way
+    pub fn set_chess960(&mut self, chess960: bool) {
+        self.chess960 = chess960;
+    }
+    pub fn get_active_pieces(&self) -> Vec<Position> {
+        self.pieces(self.active_color).collect()
+    }
+    /// iterates the squares of a color's pieces, without collecting them first
+    pub fn pieces(&self, color: PieceColor) -> Positions {
+        positions(self.get_color_bitboard(color))
+    }
+    pub fn set_piece(&mut self, on: Position, value: u32) {
+        let bit = square_bit(on);
+        let old = self.squares[on[0]][on[1]];
+        if old.is_piece() {
+            self.pieces[piece_index(old.0)] &= !bit;
+            self.colors[color_index(old.get_color())] &= !bit;
+            self.zobrist ^= piece_zobrist(old, on);
+        }
+        let new = Piece(value);
+        if new.is_piece() {
+            self.pieces[piece_index(new.0)] |= bit;
+            self.colors[color_index(new.get_color())] |= bit;
+            self.zobrist ^= piece_zobrist(new, on);
+        }
+        self.squares[on[0]][on[1]] = new;
+    }
+    /// makes a move with uci info
+    ///
+    /// returns true if move was successful
+    pub fn uci_move_turn(&mut self, uci_move: UciMove) -> Result<()> {
+        self.single_move_turn(uci_move)
+    }
+    /// makes a single move with check
+    ///
+    /// en passant is detected from the board and a promotion without a piece becomes a queen
+    ///
+    /// returns true if move was successful
+    pub fn single_move_turn(&mut self, position_move: PositionMove) -> Result<()> {
+        let position_move = self.with_board_context(position_move);
+        self.is_valid_move(position_move)?;
+        self.make_move(position_move);
+        Ok(())
+    }
+    /// check if move is valid
+    pub fn is_valid_move(&self, piece_move: PositionMove) -> Result<()> {
+        let PositionMove {
+            from,
+            to,
+            promotion,
+            ..
+        } = piece_move;
+        let piece_from = self.squares[from[0]][from[1]];
+        let piece_to = self.squares[to[0]][to[1]];
+
+        if !piece_from.is_piece() {
+            return Err(anyhow!(PieceMoveError::EmptySquare));
+        }
+
+        if piece_from.get_color() != self.active_color {
+            return Err(anyhow!(PieceMoveError::NotYourPiece(piece_from, from)));
+        }
+
+        if piece_to.is_piece()
+            && piece_to.get_color() == self.active_color
+            && !self.is_castling(from, to)
+        {
+            return Err(anyhow!(PieceMoveError::NotYourPiece(piece_to, to)));
+        }
+
+        if let Some(piece_type) = promotion {
+            if !self.is_promotion(from, to) || !PROMOTION_PIECES.contains(&piece_type) {
+                return Err(anyhow!(PieceMoveError::InvalidPromotion(piece_type)));
+            }
+        }
+
+        let moves = self.gen_legal_positions(from);
+
+        if !moves.contains(&to) {
+            return Err(anyhow!(PieceMoveError::MoveNotFound));
+        }
+
+        Ok(())
+    }
+    pub fn is_hit(&self, pos: Position) -> bool {
+        let piece_on = self.get_piece(pos);
+        piece_on.is_piece() && piece_on.get_color() != self.active_color
+    }
+    pub fn is_in_check(&self, color: PieceColor) -> bool {
+        positions(self.get_bitboard(color, PieceType::King))
+            .any(|king| self.is_square_attacked(king, color.opposite()))
+    }
+    /// checks if any piece of `by_color` attacks the square, pinned pieces included
+    pub fn is_square_attacked(&self, square: Position, by_color: PieceColor) -> bool {
+        self.attackers(square, by_color, self.get_occupied()) != EMPTY
+    }
+    /// all pieces of `by_color` attacking the square, pinned pieces included
+    pub fn attackers_of(&self, square: Position, by_color: PieceColor) -> Vec<Position> {
+        positions(self.attackers(square, by_color, self.get_occupied())).collect()
+    }
+    /// generate only legal move positions for piece
+    pub fn gen_legal_positions(&self, from: Position) -> Vec<Position> {
+        let safety = self.king_safety(self.get_piece(from).get_color());
+        positions(self.legal_targets(from, &safety)).collect()
+    }
+    /// generate all possible move position for piece
+    pub fn gen_to_positions(&self, from: Position) -> Vec<Position> {
+        positions(self.gen_targets(from)).collect()
+    }
+    pub fn gen_sliding(&self, from: Position, piece_type: PieceType) -> Vec<Position> {
+        positions(self.sliding_targets(from, piece_type)).collect()
+    }
+    pub fn gen_king(&self, from: Position) -> Vec<Position> {
+        positions((king_attacks(from) & !self.own_pieces(from)) | self.castling_targets(from))
+            .collect()
+    }
+    pub fn gen_pawn(&self, from: Position) -> Vec<Position> {
+        positions(self.pawn_targets(from)).collect()
+    }
+    pub fn gen_knight(&self, from: Position) -> Vec<Position> {
+        positions(knight_attacks(from) & !self.own_pieces(from)).collect()
+    }
+    /// make a move (without check) and hand the turn to the opponent
+    ///
+    /// a move without board context, like one from [`PositionMove::new`], is filled in first.
+    /// the returned [`Undo`] takes the board back with [`BoardMap::unmake_move`]
+    pub fn make_move(&mut self, position_move: PositionMove) -> Undo {
+        let position_move = if position_move.has_board_context() {
+            position_move
+        } else {
+            self.with_board_context(position_move)
+        };
+        let PositionMove {
+            from,
+            to,
+            promotion,
+            ..
+        } = position_move;
+        let moved = self.get_piece(from);
+        let captured_position = if position_move.is_en_passant() {
+            [from[0], to[1]]
+        } else {
+            to
+        };
+        let undo = Undo {
+            position_move,
+            moved,
+            captured: self.get_piece(captured_position),
+            castling_rights: self.castling_rights,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            zobrist: self.zobrist,
+        };
+        self.zobrist ^= self.en_passant_zobrist();
+
+        if Self::resets_halfmove_clock(position_move) {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+        if self.active_color == PieceColor::Black {
+            self.fullmove_number += 1;
+        }
+
+        // only the pawn that just made a double step can be taken en passant
+        if let Some(target) = self.en_passant.take() {
+            let pawn = self.square_mut(en_passant_pawn(target));
+            if let Some(PieceType::Pawn(true)) = pawn.get_type() {
+                pawn.0 -= 32;
+            }
+        }
+        if position_move.is_castle() {
+            // both leave first, in Chess960 the king can land where the rook stood and back
+            let (king_to, rook_from, rook_to) = self.castling_files(position_move);
+            let rook = self.get_piece([from[0], rook_from]);
+            self.set_piece(from, 0);
+            self.set_piece([from[0], rook_from], 0);
+            self.set_piece([from[0], king_to], moved.0);
+            self.set_piece([from[0], rook_to], rook.0);
+            self.update_castling_rights(position_move);
+        } else {
+            self.update_castling_rights(position_move);
+            self.set_piece(captured_position, 0);
+            if let Some(piece_type) = promotion {
+                self.set_piece(to, piece_type.to_value() | moved.get_color().to_value());
+            } else {
+                self.set_piece(to, moved.0);
+            }
+            self.set_piece(from, 0);
+        }
+
+        if position_move.is_double_push() {
+            self.square_mut(to).0 += 32;
+            self.en_passant = Some([(from[0] + to[0]) / 2, to[1]]);
+        }
+
+        self.switch_active_color();
+        self.zobrist ^= self.en_passant_zobrist();
+        undo
+    }
+    /// takes back the move [`BoardMap::make_move`] returned the [`Undo`] for,
+    /// has to be called in reverse order of the moves made
+    pub fn unmake_move(&mut self, undo: Undo) {
+        let position_move = undo.position_move;
+        let PositionMove { from, to, .. } = position_move;
+        self.switch_active_color();
+
+        if position_move.is_castle() {
+            self.castling_rights = undo.castling_rights;
+            let (king_to, rook_from, rook_to) = self.castling_files(position_move);
+            let rook = self.get_piece([from[0], rook_to]);
+            self.set_piece([from[0], king_to], 0);
+            self.set_piece([from[0], rook_to], 0);
+            self.set_piece([from[0], rook_from], rook.0);
+            self.set_piece(from, undo.moved.0);
+        } else {
+            self.set_piece(to, 0);
+            let captured_position = if position_move.is_en_passant() {
+                [from[0], to[1]]
+            } else {
+                to
+            };
+            self.set_piece(captured_position, undo.captured.0);
+            self.set_piece(from, undo.moved.0);
+        }
+
+        if let Some(target) = undo.en_passant {
+            let pawn = self.square_mut(en_passant_pawn(target));
+            if let Some(PieceType::Pawn(false)) = pawn.get_type() {
+                pawn.0 += 32;
+            }
+        }
+        self.castling_rights = undo.castling_rights;
+        self.en_passant = undo.en_passant;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.fullmove_number = undo.fullmove_number;
+        self.zobrist = undo.zobrist;
+    }
+    /// generates all moves based on active color.
+    ///
+    /// a promotion is generated once for every piece the pawn can turn into
+    pub fn gen_all_legal_moves(&self) -> Vec<PositionMove> {
+        self.legal_moves().to_vec()
+    }
+    /// generates only the captures, en passant included, and promotions of the active color
+    pub fn gen_all_legal_captures(&self) -> Vec<PositionMove> {
+        self.legal_captures().to_vec()
+    }
+    /// same as [`BoardMap::gen_all_legal_moves`] without allocating
+    pub fn legal_moves(&self) -> MoveList {
+        let mut legal_moves = MoveList::new();
+        self.gen_legal_moves(&mut legal_moves, false);
+        legal_moves
+    }
+    /// same as [`BoardMap::gen_all_legal_captures`] without allocating
+    pub fn legal_captures(&self) -> MoveList {
+        let mut legal_captures = MoveList::new();
+        self.gen_legal_moves(&mut legal_captures, true);
+        legal_captures
+    }
+    /// checks if the move takes a piece, en passant included
+    pub fn is_capture(&self, position_move: PositionMove) -> bool {
+        position_move.is_en_passant() || self.is_hit(position_move.to)
+    }
+    pub fn gen_all_opponent_positions(&self) -> Vec<Position> {
+        positions(self.get_color_bitboard(self.active_color.opposite()))
+            .flat_map(|from| self.gen_to_positions(from))
+            .collect()
+    }
+    /// a pawn moving onto the en passant target square, taking the pawn beside it
+    pub fn is_en_passant(&self, from: Position, to: Position) -> bool {
+        let piece = self.get_piece(from);
+        if self.en_passant != Some(to) || !matches!(piece.get_type(), Some(PieceType::Pawn(_))) {
+            return false;
+        }
+        let step_piece = self.get_piece([from[0], to[1]]);
+        matches!(step_piece.get_type(), Some(PieceType::Pawn(_)))
+            && step_piece.get_color() != piece.get_color()
+    }
+    pub fn is_promotion(&self, from: Position, to: Position) -> bool {
+        let piece = self.get_piece(from);
+        if let Some(piece_type) = piece.get_type() {
+            return (piece_type == PieceType::Pawn(false) || piece_type == PieceType::Pawn(true))
+                && (to[0] == 7 || to[0] == 0);
+        }
+
+        false
+    }
+    /// checks if the game is over for the active color, apart from repetitions
+    /// which need the history kept by [`crate::Game`]
+    pub fn outcome(&self) -> Outcome {
+        if self.legal_moves().is_empty() {
+            return if self.is_in_check(self.active_color) {
+                Outcome::Checkmate(self.active_color.opposite())
+            } else {
+                Outcome::Stalemate
+            };
+        }
+        if self.is_insufficient_material() {
+            return Outcome::InsufficientMaterial;
+        }
+        if self.halfmove_clock >= 150 {
+            return Outcome::FiftyMoveRule;
+        }
+        Outcome::Ongoing
+    }
+    /// neither side can ever checkmate: only kings are left with a single knight,
+    /// or with bishops that all stand on the same square color
+    pub fn is_insufficient_material(&self) -> bool {
+        let piece_bitboard = |value: u32| self.pieces[piece_index(value)];
+        if piece_bitboard(PAWN) | piece_bitboard(ROOK) | piece_bitboard(QUEEN) != EMPTY {
+            return false;
+        }
+        let knights = piece_bitboard(KNIGHT).count_ones();
+        let bishops = piece_bitboard(BISHOP);
+        let bishop_square_colors = [
+            bishops & LIGHT_SQUARES != EMPTY,
+            bishops & DARK_SQUARES != EMPTY,
+        ];
+        match (knights, bishop_square_colors) {
+            (0, [true, true]) => false,
+            (0, _) => true,
+            (1, [false, false]) => true,
+            _ => false,
+        }
+    }
+    // f(p) = 200(K-K')
+    //        + 9(Q-Q')
+    //        + 5(R-R')
+    //        + 3(B-B' + N-N')
+    //        + 1(P-P')
+    //        - 0.5(D-D' + S-S' + I-I')
+    //        + 0.1(M-M') + ...
+    // KQRBNP = number of kings, queens, rooks, bishops, knights and pawns
+    //  D,S,I = doubled, blocked and isolated pawns
+    //  M = Mobility (the number of legal moves)
+    //
+    // counted for the active color, so a positive weight means the side to move is ahead
+    pub fn get_material_weight(&self) -> i32 {
+        const WEIGHTS: [(PieceType, i32); 6] = [
+            (PieceType::Pawn(false), 1),
+            (PieceType::Knight, 3),
+            (PieceType::Bishop, 3),
+            (PieceType::Rook, 5),
+            (PieceType::Queen, 9),
+            (PieceType::King, 200),
+        ];
+        let opponent = self.active_color.opposite();
+        WEIGHTS.iter().fold(0, |res, &(piece_type, weight)| {
+            let own = self
+                .get_bitboard(self.active_color, piece_type)
+                .count_ones() as i32;
+            let other = self.get_bitboard(opponent, piece_type).count_ones() as i32;
+            res + weight * (own - other)
+        })
+    }
+    pub fn get_num_white_pieces(&self) -> i32 {
+        self.get_color_bitboard(PieceColor::White).count_ones() as i32
+    }
+    pub fn get_num_black_pieces(&self) -> i32 {
+        self.get_color_bitboard(PieceColor::Black).count_ones() as i32
+    }
+    pub fn switch_active_color(&mut self) {
+        self.active_color = self.active_color.opposite();
+        self.zobrist ^= zobrist::side_key();
+    }
+    /// fills in what a move implies on this board: the moving and captured pieces,
+    /// the flags and the default queen promotion
+    pub(crate) fn with_board_context(&self, mut position_move: PositionMove) -> PositionMove {
+        let PositionMove { from, to, .. } = position_move;
+        let piece = self.get_piece(from);
+        position_move.piece = piece;
+        position_move.flags = 0;
+        if self.is_castling(from, to) {
+            position_move.flags |= CASTLE;
+            position_move.captured = Piece::default();
+        } else if self.is_en_passant(from, to) {
+            position_move.flags |= EN_PASSANT;
+            position_move.captured = self.get_piece([from[0], to[1]]);
+        } else {
+            position_move.captured = self.get_piece(to);
+        }
+        if matches!(piece.get_type(), Some(PieceType::Pawn(_))) {
+            if from[0].abs_diff(to[0]) == 2 {
+                position_move.flags |= DOUBLE_PUSH;
+            }
+            if position_move.promotion.is_none() && (to[0] == 0 || to[0] == 7) {
+                position_move.promotion = Some(PieceType::Queen);
+            }
+        }
+        position_move
+    }
+
+    /// captures and pawn moves reset the halfmove clock
+    fn resets_halfmove_clock(position_move: PositionMove) -> bool {
+        position_move.is_capture()
+            || matches!(position_move.piece.get_type(), Some(PieceType::Pawn(_)))
+    }
+
+    /// a king moving two squares sideways, or in Chess960 onto its own rook
+    fn is_castling(&self, from: Position, to: Position) -> bool {
+        let king = self.get_piece(from);
+        if king.get_type() != Some(PieceType::King) || from[0] != to[0] {
+            return false;
+        }
+        if self.chess960 {
+            let rook = self.get_piece(to);
+            rook.get_type() == Some(PieceType::Rook) && rook.get_color() == king.get_color()
+        } else {
+            from[1].abs_diff(to[1]) == 2
+        }
+    }
+
+    /// king destination, rook start and rook destination files of a castling move
+    fn castling_files(&self, position_move: PositionMove) -> (usize, usize, usize) {
+        let PositionMove { from, to, .. } = position_move;
+        let short = to[1] > from[1];
+        let (king_to, rook_to) = castled_files(short);
+        let rook_from = self
+            .castling_rights
+            .rook_file(position_move.piece.get_color(), short);
+        (king_to, rook_from, rook_to)
+    }
+
+    /// moving the king removes both its castling rights,
+    /// moving from or capturing on a castling rook's square removes that right
+    fn update_castling_rights(&mut self, position_move: PositionMove) {
+        let PositionMove {
+            from, to, piece, ..
+        } = position_move;
+        self.zobrist ^= zobrist::castling_key(self.castling_rights);
+        if piece.get_type() == Some(PieceType::King) {
+            self.castling_rights.remove(piece.get_color());
+        }
+        let rights = self.castling_rights;
+        for (color, short, right) in [
+            (
+                PieceColor::White,
+                true,
+                &mut self.castling_rights.white_short,
+            ),
+            (
+                PieceColor::White,
+                false,
+                &mut self.castling_rights.white_long,
+            ),
+            (
+                PieceColor::Black,
+                true,
+                &mut self.castling_rights.black_short,
+            ),
+            (
+                PieceColor::Black,
+                false,
+                &mut self.castling_rights.black_long,
+            ),
+        ] {
+            let rook = [home_row(color), rights.rook_file(color, short)];
+            if from == rook || to == rook {
+                *right = false;
+            }
+        }
+        self.zobrist ^= zobrist::castling_key(self.castling_rights);
+    }
+
+    /// the en passant file only counts when a pawn could actually take there
+    fn en_passant_zobrist(&self) -> u64 {
+        match self.en_passant {
+            Some(target)
+                if pawn_attacks(target, self.active_color.opposite())
+                    & self.get_bitboard(self.active_color, PieceType::Pawn(false))
+                    != EMPTY =>
+            {
+                zobrist::en_passant_key(target[1])
+            }
+            _ => 0,
+        }
+    }
+
+    /// only changes the piece value itself, the piece type and color have to stay the same
+    fn square_mut(&mut self, pos: Position) -> &mut Piece {
+        &mut self.squares[pos[0]][pos[1]]
+    }
+
+    /// builds the bitboards and zobrist key again after the squares were written directly
+    fn sync_squares(&mut self) {
+        self.pieces = [EMPTY; 6];
+        self.colors = [EMPTY; 2];
+        for (row, pieces) in self.squares.iter().enumerate() {
+            for (col, piece) in pieces.iter().enumerate() {
+                if piece.is_piece() {
+                    self.pieces[piece_index(piece.0)] |= square_bit([row, col]);
+                    self.colors[color_index(piece.get_color())] |= square_bit([row, col]);
+                }
+            }
+        }
+        self.zobrist = self.compute_zobrist();
+    }
+
+    /// hashes the position from scratch, [`BoardMap::zobrist`] keeps it up to date from there
+    fn compute_zobrist(&self) -> u64 {
+        let mut key = self.en_passant_zobrist() ^ zobrist::castling_key(self.castling_rights);
+        if self.active_color == PieceColor::Black {
+            key ^= zobrist::side_key();
+        }
+        for position in positions(self.get_occupied()) {
+            key ^= piece_zobrist(self.get_piece(position), position);
+        }
+        key
+    }
+
+    /// the king and rook have to be home with the right still there, every square either
+    /// of them crosses or lands on empty and the king can't castle out of, through or into check.
+    /// In Chess960 the king and rook can start anywhere on the back rank
+    fn can_castle(&self, color: PieceColor, short: bool) -> bool {
+        let can_castle = if short {
+            self.castling_rights.can_short_castle(color)
+        } else {
+            self.castling_rights.can_long_castle(color)
+        };
+        let row = home_row(color);
+        let Some(king_from) = positions(self.get_bitboard(color, PieceType::King))
+            .find(|king| king[0] == row)
+            .map(|king| king[1])
+        else {
+            return false;
+        };
+        let rook_from = self.castling_rights.rook_file(color, short);
+        let rook = self.squares[row][rook_from];
+        if !can_castle
+            || rook.get_type() != Some(PieceType::Rook)
+            || rook.get_color() != color
+            || (rook_from > king_from) != short
+        {
+            return false;
+        }
+
+        let (king_to, rook_to) = castled_files(short);
+        // the castling pieces don't block each other, and the rook doesn't shield the king
+        let occupied =
+            self.get_occupied() & !square_bit([row, king_from]) & !square_bit([row, rook_from]);
+        let files = |a: usize, b: usize| a.min(b)..=a.max(b);
+        if files(king_from, king_to)
+            .chain(files(rook_from, rook_to))
+            .any(|file| occupied & square_bit([row, file]) != EMPTY)
+        {
+            return false;
+        }
+        let opponent = color.opposite();
+        !files(king_from, king_to)
+            .any(|file| self.attackers([row, file], opponent, occupied) != EMPTY)
+    }
+
+    /// pieces of `by_color` attacking the square, looking outward from the square
+    /// so king moves (and their castling) never have to be generated
+    fn attackers(&self, square: Position, by_color: PieceColor, occupied: Bitboard) -> Bitboard {
+        let piece_bitboard = |value: u32| self.pieces[piece_index(value)];
+        let diagonal = piece_bitboard(BISHOP) | piece_bitboard(QUEEN);
+        let orthogonal = piece_bitboard(ROOK) | piece_bitboard(QUEEN);
+        // pawns attack the square from where a pawn of the other color would capture
+        let attackers = (pawn_attacks(square, by_color.opposite()) & piece_bitboard(PAWN))
+            | (knight_attacks(square) & piece_bitboard(KNIGHT))
+            | (king_attacks(square) & piece_bitboard(KING))
+            | (bishop_attacks(square, occupied) & diagonal)
+            | (rook_attacks(square, occupied) & orthogonal);
+        attackers & self.get_color_bitboard(by_color)
+    }
+
+    /// checks the move on the bitboards alone, without playing it on a copy of the board
+    fn leaves_king_in_check(&self, from: Position, to: Position) -> bool {
+        let piece = self.get_piece(from);
+        let color = piece.get_color();
+        let king = if piece.get_type() == Some(PieceType::King) {
+            square_bit(to)
+        } else {
+            self.get_bitboard(color, PieceType::King)
+        };
+        let mut captured = square_bit(to);
+        if self.is_en_passant(from, to) {
+            captured |= square_bit([from[0], to[1]]);
+        }
+        let occupied = (self.get_occupied() & !square_bit(from) & !captured) | square_bit(to);
+        positions(king)
+            .any(|king| self.attackers(king, color.opposite(), occupied) & !captured != EMPTY)
+    }
+
+    /// the pins and checks are worked out once, every move is then legal by construction.
+    /// Fills a list the caller keeps, so the search can reuse one for every ply
+    pub(crate) fn gen_legal_moves(&self, legal_moves: &mut MoveList, captures_only: bool) {
+        legal_moves.clear();
+        let safety = self.king_safety(self.active_color);
+        let opponent = self.get_color_bitboard(self.active_color.opposite());
+        for from_move in self.pieces(self.active_color) {
+            for to in positions(self.legal_targets(from_move, &safety)) {
+                let position_move = self.with_board_context(PositionMove::new(from_move, to));
+                let promotion = position_move.promotion.is_some();
+                if captures_only
+                    && !promotion
+                    && !position_move.is_en_passant()
+                    && opponent & square_bit(to) == EMPTY
+                {
+                    continue;
+                }
+                if promotion {
+                    legal_moves.extend(
+                        PROMOTION_PIECES
+                            .iter()
+                            .map(|&piece_type| position_move.with_promotion(piece_type)),
+                    );
+                } else {
+                    legal_moves.push(position_move);
+                }
+            }
+        }
+    }
+
+    /// squares the piece on `from` can move to, without looking at its own king
+    fn gen_targets(&self, from: Position) -> Bitboard {
+        match self.get_piece(from).get_type() {
+            Some(piece_type @ (PieceType::Bishop | PieceType::Rook | PieceType::Queen)) => {
+                self.sliding_targets(from, piece_type)
+            }
+            Some(PieceType::Pawn(_)) => self.pawn_targets(from),
+            Some(PieceType::King) => {
+                (king_attacks(from) & !self.own_pieces(from)) | self.castling_targets(from)
+            }
+            Some(PieceType::Knight) => knight_attacks(from) & !self.own_pieces(from),
+            None => EMPTY,
+        }
+    }
+
+    fn sliding_targets(&self, from: Position, piece_type: PieceType) -> Bitboard {
+        let occupied = self.get_occupied();
+        let attacks = match piece_type {
+            PieceType::Bishop => bishop_attacks(from, occupied),
+            PieceType::Rook => rook_attacks(from, occupied),
+            _ => queen_attacks(from, occupied),
+        };
+        attacks & !self.own_pieces(from)
+    }
+
+    fn pawn_targets(&self, from: Position) -> Bitboard {
+        let color = self.get_piece(from).get_color();
+        let occupied = self.get_occupied();
+        let (forward, start_row) = match color {
+            PieceColor::Black => (1, 1),
+            PieceColor::White => (-1, 6),
+        };
+        let mut targets = EMPTY;
+
+        // pushes, a pawn on the last rank has nowhere to go
+        let single = [(from[0] as i32 + forward) as usize, from[1]];
+        if single[0] < 8 && occupied & square_bit(single) == EMPTY {
+            targets |= square_bit(single);
+            let double = [(single[0] as i32 + forward) as usize, from[1]];
+            if from[0] == start_row && occupied & square_bit(double) == EMPTY {
+                targets |= square_bit(double);
+            }
+        }
+
+        // captures, en passant included
+        let mut captures = self.get_color_bitboard(color.opposite());
+        if let Some(en_passant) = self
+            .en_passant
+            .filter(|&target| self.is_en_passant(from, target))
+        {
+            captures |= square_bit(en_passant);
+        }
+        targets | (pawn_attacks(from, color) & captures)
+    }
+
+    /// castling, onto the king's square in standard chess and onto the rook in Chess960
+    fn castling_targets(&self, from: Position) -> Bitboard {
+        let color = self.get_piece(from).get_color();
+        let mut targets = EMPTY;
+        for short in [true, false] {
+            if self.can_castle(color, short) {
+                let (king_to, _) = castled_files(short);
+                let to = if self.chess960 {
+                    self.castling_rights.rook_file(color, short)
+                } else {
+                    king_to
+                };
+                targets |= square_bit([from[0], to]);
+            }
+        }
+        targets
+    }
+
+    /// pieces of the same color as the piece on `from`
+    fn own_pieces(&self, from: Position) -> Bitboard {
+        self.get_color_bitboard(self.get_piece(from).get_color())
+    }
+}
+
+/// index into the piece bitboards for a piece value
+fn piece_index(value: u32) -> usize {
+    (value % 8) as usize - 1
+}
+
+fn piece_zobrist(piece: Piece, position: Position) -> u64 {
+    zobrist::piece_key(
+        color_index(piece.get_color()),
+        piece_index(piece.0),
+        square_index(position),
+    )
+}
+
+/// the pawn that made the double step over the en passant target square
+fn en_passant_pawn(target: Position) -> Position {
+    [if target[0] == 2 { 3 } else { 4 }, target[1]]
+}
+
+/// king and rook files after castling, the same in standard chess and Chess960
+fn castled_files(short: bool) -> (usize, usize) {
+    if short {
+        (6, 5)
+    } else {
+        (2, 3)
+    }
+}
+
+/// the row a color's pieces start on
+fn home_row(color: PieceColor) -> usize {
+    match color {
+        PieceColor::White => 7,
+        PieceColor::Black => 0,
+    }
+}
+
+impl Debug for BoardMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for i in 0..8 {
+            writeln!(f, "{} {:?}", 8 - i, self.squares[i]).unwrap();
+        }
+        writeln!(f, "    a    b    c    d    e    f    g    h").unwrap();
+        writeln!(f, "fen: {}", self.get_fen()).unwrap();
+        writeln!(
+            f,
+            "{}'s turn",
+            match self.active_color {
+                PieceColor::Black => "black",
+                PieceColor::White => "white",
+            }
+        )
+        .unwrap();
+
+        Ok(())
+    }
+}
+
+/// boards are equal when they hold the same position, whatever the move clocks say
+impl PartialEq for BoardMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.zobrist == other.zobrist
+            && self.pieces == other.pieces
+            && self.colors == other.colors
+            && self.active_color == other.active_color
+            && self.castling_rights == other.castling_rights
+            && self.en_passant_zobrist() == other.en_passant_zobrist()
+    }
+}
+
+impl Eq for BoardMap {}
+
+impl Hash for BoardMap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.zobrist);
+    }
+}
+
+impl Deref for BoardMap {
+    type Target = [[Piece; 8]; 8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.squares
+    }
+}
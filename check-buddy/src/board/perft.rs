@@ -0,0 +1,67 @@
+use super::BoardMap;
+use crate::moves::position_move::PositionMove;
+use crate::TranspositionTable;
+
+impl BoardMap {
+    /// counts the positions reached after exactly `depth` legal moves
+    pub fn perft(&self, depth: usize) -> u64 {
+        let mut board = *self;
+        board.perft_nodes(depth, None)
+    }
+    /// perft that looks up and stores the count of every position it passes,
+    /// so transpositions are only counted once
+    pub fn perft_with_table(&self, depth: usize, table: &mut TranspositionTable<u64>) -> u64 {
+        let mut board = *self;
+        board.perft_nodes(depth, Some(table))
+    }
+    /// perft split up by the first move, to find the move a wrong count comes from
+    pub fn perft_divide(&self, depth: usize) -> Vec<(PositionMove, u64)> {
+        if depth == 0 {
+            return vec![];
+        }
+        let mut board = *self;
+        board
+            .legal_moves()
+            .into_iter()
+            .map(|position_move| {
+                let undo = board.make_move(position_move);
+                let nodes = board.perft_nodes(depth - 1, None);
+                board.unmake_move(undo);
+                (position_move, nodes)
+            })
+            .collect()
+    }
+
+    fn perft_nodes(
+        &mut self,
+        depth: usize,
+        mut table: Option<&mut TranspositionTable<u64>>,
+    ) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let key = self.zobrist();
+        if let Some(entry) = table
+            .as_deref()
+            .and_then(|table| table.get(key))
+            .filter(|entry| entry.depth as usize == depth)
+        {
+            return entry.value;
+        }
+
+        let moves = self.legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+        let mut nodes = 0;
+        for position_move in moves {
+            let undo = self.make_move(position_move);
+            nodes += self.perft_nodes(depth - 1, table.as_deref_mut());
+            self.unmake_move(undo);
+        }
+        if let Some(table) = table {
+            table.insert(key, depth, nodes);
+        }
+        nodes
+    }
+}
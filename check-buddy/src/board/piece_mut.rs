@@ -0,0 +1,77 @@
+use super::BoardMap;
+use crate::moves::position_move::Position;
+use crate::piece::Piece;
+use std::ops::{Deref, DerefMut};
+
+/// a piece taken off the board by [`BoardMap::get_piece_mut`] to be changed,
+/// it's put back with [`BoardMap::set_piece`] when dropped so the bitboards follow
+#[derive(Debug)]
+pub struct PieceMut<'a> {
+    board: &'a mut BoardMap,
+    position: Position,
+    piece: Piece,
+}
+
+impl<'a> PieceMut<'a> {
+    pub(super) fn new(board: &'a mut BoardMap, position: Position) -> Self {
+        let piece = board.get_piece(position);
+        Self {
+            board,
+            position,
+            piece,
+        }
+    }
+}
+
+impl Deref for PieceMut<'_> {
+    type Target = Piece;
+
+    fn deref(&self) -> &Self::Target {
+        &self.piece
+    }
+}
+
+impl DerefMut for PieceMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.piece
+    }
+}
+
+impl Drop for PieceMut<'_> {
+    fn drop(&mut self) {
+        self.board.set_piece(self.position, self.piece.0);
+    }
+}
+
+/// all squares of a board to be changed at once, handed out by [`BoardMap::squares_mut`].
+/// The bitboards and zobrist key are built again from the squares when it's dropped
+#[derive(Debug)]
+pub struct SquaresMut<'a> {
+    board: &'a mut BoardMap,
+}
+
+impl<'a> SquaresMut<'a> {
+    pub(super) fn new(board: &'a mut BoardMap) -> Self {
+        Self { board }
+    }
+}
+
+impl Deref for SquaresMut<'_> {
+    type Target = [[Piece; 8]; 8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.board.squares
+    }
+}
+
+impl DerefMut for SquaresMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.board.squares
+    }
+}
+
+impl Drop for SquaresMut<'_> {
+    fn drop(&mut self) {
+        self.board.sync_squares();
+    }
+}
@@ -0,0 +1,202 @@
+use super::BoardMap;
+use crate::errors::UciMoveError;
+use crate::moves::position_move::{notation_to_position, position_to_notation, PositionMove};
+use crate::piece_type::PieceType;
+use crate::uci_move::UciMove;
+
+impl BoardMap {
+    /// parses a legal move in standard algebraic notation like `Nbd7`, `exd6`, `e8=Q+` or `O-O`.
+    /// `e8Q` and `0-0` are accepted as well and annotations like `!?` are skipped.
+    /// A move that fits more than one piece, or whose `x` doesn't match the capture, is refused
+    pub fn parse_uci_to_move(&self, san: &str) -> Result<UciMove, UciMoveError> {
+        let san = san.trim().trim_end_matches(['!', '?']);
+        let san = san.strip_suffix(['+', '#']).unwrap_or(san);
+        if san.is_empty() {
+            return Err(UciMoveError::Empty);
+        }
+        if !san.is_ascii() {
+            return Err(UciMoveError::InvalidUciMove);
+        }
+        let legal_moves = self.legal_moves();
+
+        let castling = match san {
+            "O-O" | "0-0" => Some(false),
+            "O-O-O" | "0-0-0" => Some(true),
+            _ => None,
+        };
+        if let Some(long) = castling {
+            return legal_moves
+                .into_iter()
+                .find(|position_move| {
+                    position_move.is_castle()
+                        && (position_move.to[1] < position_move.from[1]) == long
+                })
+                .ok_or(UciMoveError::FromNotFound);
+        }
+
+        let (piece_type, rest) = match symbol_piece(san.as_bytes()[0] as char) {
+            Some(piece_type) => (piece_type, &san[1..]),
+            None => (PieceType::Pawn(false), san),
+        };
+        let is_pawn = matches!(piece_type, PieceType::Pawn(_));
+        let (rest, promotion) = match rest.chars().next_back() {
+            Some(symbol) if is_pawn && symbol.is_ascii_uppercase() => {
+                let promotion = symbol_piece(symbol)
+                    .filter(|piece_type| *piece_type != PieceType::King)
+                    .ok_or(UciMoveError::InvalidPromotionPiece(symbol))?;
+                let rest = &rest[..rest.len() - 1];
+                (rest.strip_suffix('=').unwrap_or(rest), Some(promotion))
+            }
+            _ => (rest, None),
+        };
+        if rest.len() < 2 {
+            return Err(UciMoveError::InvalidUciMove);
+        }
+        let (prefix, destination) = rest.split_at(rest.len() - 2);
+        let to = notation_to_position(destination).ok_or(UciMoveError::ToNotFound)?;
+        let (prefix, take) = match prefix.strip_suffix('x') {
+            Some(prefix) => (prefix, true),
+            None => (prefix, false),
+        };
+        let (file, rank) = parse_disambiguation(prefix)?;
+        // pawns only name their file when they capture
+        if is_pawn && (rank.is_some() || file.is_some() != take) {
+            return Err(UciMoveError::InvalidUciMove);
+        }
+
+        let mut candidates = legal_moves.into_iter().filter(|position_move| {
+            let PositionMove { from, .. } = *position_move;
+            let moving = position_move.piece.get_type();
+            let same_type = match piece_type {
+                PieceType::Pawn(_) => matches!(moving, Some(PieceType::Pawn(_))),
+                _ => moving == Some(piece_type),
+            };
+            // a pawn reaching the last rank without a piece letter becomes a queen
+            let default_promotion = position_move.promotion.map(|_| PieceType::Queen);
+            position_move.to == to
+                && same_type
+                && position_move.is_capture() == take
+                && file.unwrap_or(if is_pawn { to[1] } else { from[1] }) == from[1]
+                && rank.is_none_or(|rank| rank == from[0])
+                && position_move.promotion == promotion.or(default_promotion)
+        });
+        let position_move = candidates.next().ok_or(UciMoveError::FromNotFound)?;
+        if candidates.next().is_some() {
+            return Err(UciMoveError::AmbiguousMove(san.to_string()));
+        }
+        Ok(position_move)
+    }
+    /// writes a legal move in standard algebraic notation, e.g. `Nbd7`, `exd6`, `e8=Q+` or `O-O#`.
+    /// The starting square is only added as far as needed to tell two pieces apart
+    pub fn to_san(&self, position_move: PositionMove) -> String {
+        let position_move = self.with_board_context(position_move);
+        let PositionMove {
+            from,
+            to,
+            promotion,
+            ..
+        } = position_move;
+        let Some(piece_type) = self.get_piece(from).get_type() else {
+            return String::new();
+        };
+
+        let mut san = if position_move.is_castle() {
+            if to[1] > from[1] { "O-O" } else { "O-O-O" }.to_string()
+        } else {
+            let capture = if position_move.is_capture() { "x" } else { "" };
+            let destination = position_to_notation(to);
+            match piece_type {
+                PieceType::Pawn(_) => {
+                    let file = if capture.is_empty() {
+                        String::new()
+                    } else {
+                        position_to_notation(from)[..1].to_string()
+                    };
+                    let promotion = promotion
+                        .map(|piece_type| format!("={}", symbol(piece_type)))
+                        .unwrap_or_default();
+                    format!("{file}{capture}{destination}{promotion}")
+                }
+                _ => format!(
+                    "{}{}{capture}{destination}",
+                    symbol(piece_type),
+                    self.disambiguation(position_move, piece_type)
+                ),
+            }
+        };
+
+        let mut board = *self;
+        board.make_move(position_move);
+        if board.is_in_check(*board.get_active_color()) {
+            san.push(if board.legal_moves().is_empty() {
+                '#'
+            } else {
+                '+'
+            });
+        }
+        san
+    }
+
+    /// the file, else the rank, else both of the starting square when
+    /// another piece of the same type can move to the same square
+    fn disambiguation(&self, position_move: PositionMove, piece_type: PieceType) -> String {
+        let PositionMove { from, to, .. } = position_move;
+        let others = self
+            .legal_moves()
+            .into_iter()
+            .filter(|other| {
+                other.to == to
+                    && other.from != from
+                    && self.get_piece(other.from).get_type() == Some(piece_type)
+            })
+            .map(|other| other.from)
+            .collect::<Vec<_>>();
+        let square = position_to_notation(from);
+        if others.is_empty() {
+            String::new()
+        } else if others.iter().all(|other| other[1] != from[1]) {
+            square[..1].to_string()
+        } else if others.iter().all(|other| other[0] != from[0]) {
+            square[1..].to_string()
+        } else {
+            square
+        }
+    }
+}
+
+fn symbol_piece(symbol: char) -> Option<PieceType> {
+    match symbol {
+        'K' => Some(PieceType::King),
+        'Q' => Some(PieceType::Queen),
+        'R' => Some(PieceType::Rook),
+        'B' => Some(PieceType::Bishop),
+        'N' => Some(PieceType::Knight),
+        _ => None,
+    }
+}
+
+/// the optional file and rank in front of the destination, as column and row
+fn parse_disambiguation(prefix: &str) -> Result<(Option<usize>, Option<usize>), UciMoveError> {
+    let mut chars = prefix.chars().peekable();
+    let file = chars
+        .next_if(|c| ('a'..='h').contains(c))
+        .map(|c| (c as u8 - b'a') as usize);
+    let rank = chars
+        .next_if(|c| ('1'..='8').contains(c))
+        .map(|c| (b'8' - c as u8) as usize);
+    match chars.next() {
+        Some(_) => Err(UciMoveError::InvalidUciMove),
+        None => Ok((file, rank)),
+    }
+}
+
+fn symbol(piece_type: PieceType) -> &'static str {
+    match piece_type {
+        PieceType::Rook => "R",
+        PieceType::Pawn(_) => "",
+        PieceType::King => "K",
+        PieceType::Queen => "Q",
+        PieceType::Bishop => "B",
+        PieceType::Knight => "N",
+    }
+}
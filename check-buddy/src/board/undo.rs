@@ -0,0 +1,29 @@
+use super::castling_rights::CastlingRights;
+use crate::moves::position_move::{Position, PositionMove};
+use crate::piece::Piece;
+
+/// everything [`super::BoardMap::make_move`] changes that can't be derived from the move itself,
+/// handed back to [`super::BoardMap::unmake_move`] to restore the board exactly
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Undo {
+    pub(crate) position_move: PositionMove,
+    /// the moving piece before a promotion changed it
+    pub(crate) moved: Piece,
+    /// the captured piece, the pawn beside `to` for en passant
+    pub(crate) captured: Piece,
+    pub(crate) castling_rights: CastlingRights,
+    pub(crate) en_passant: Option<Position>,
+    pub(crate) halfmove_clock: u32,
+    pub(crate) fullmove_number: u32,
+    pub(crate) zobrist: u64,
+}
+
+impl Undo {
+    pub fn get_move(&self) -> PositionMove {
+        self.position_move
+    }
+    /// the piece the move took, if any
+    pub fn get_captured(&self) -> Option<Piece> {
+        Some(self.captured).filter(|piece| piece.is_piece())
+    }
+}
@@ -0,0 +1,54 @@
+//! fixed random keys, so a position hashes the same in every run and build
+
+use super::castling_rights::CastlingRights;
+
+pub(crate) fn piece_key(color_index: usize, piece_index: usize, square_index: usize) -> u64 {
+    KEYS[(color_index * 6 + piece_index) * 64 + square_index]
+}
+
+/// toggled every move, set while black is to move
+pub(crate) fn side_key() -> u64 {
+    KEYS[SIDE]
+}
+
+pub(crate) fn castling_key(castling_rights: CastlingRights) -> u64 {
+    let CastlingRights {
+        white_short,
+        white_long,
+        black_short,
+        black_long,
+        ..
+    } = castling_rights;
+    [white_short, white_long, black_short, black_long]
+        .iter()
+        .enumerate()
+        .filter(|(_, &enabled)| enabled)
+        .fold(0, |key, (i, _)| key ^ KEYS[CASTLING + i])
+}
+
+pub(crate) fn en_passant_key(file: usize) -> u64 {
+    KEYS[EN_PASSANT + file]
+}
+
+const SIDE: usize = 12 * 64;
+const CASTLING: usize = SIDE + 1;
+const EN_PASSANT: usize = CASTLING + 4;
+const KEY_COUNT: usize = EN_PASSANT + 8;
+
+/// splitmix64, seeded with a constant
+const fn generate_keys() -> [u64; KEY_COUNT] {
+    let mut keys = [0; KEY_COUNT];
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut i = 0;
+    while i < KEY_COUNT {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        keys[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    keys
+}
+
+static KEYS: [u64; KEY_COUNT] = generate_keys();
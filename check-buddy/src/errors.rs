@@ -1,7 +1,10 @@
 #![allow(unused)]
 
+use crate::piece_color::PieceColor;
+use crate::piece_type::PieceType;
 use crate::position_move::Position;
 use crate::Piece;
+use std::fmt::{Display, Formatter};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -22,10 +25,14 @@ pub enum PieceMoveError {
     NotYourPiece(Piece, Position),
     #[error("Move not found")]
     MoveNotFound,
+    #[error("Can't promote to {0:?} with this move")]
+    InvalidPromotion(PieceType),
 }
 
 #[derive(Error, Debug)]
 pub enum UciMoveError {
+    #[error("Move is empty")]
+    Empty,
     #[error("Couldn't convert string to digit: {0:}")]
     DigitParseFailed(String),
     #[error("Can't parse uci move")]
@@ -36,4 +43,61 @@ pub enum UciMoveError {
     FromNotFound,
     #[error("Couldn't find [to] position")]
     ToNotFound,
+    #[error("Can't promote to {0:?}")]
+    InvalidPromotionPiece(char),
+    #[error("{0} fits more than one piece")]
+    AmbiguousMove(String),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FenField {
+    Placement,
+    ActiveColor,
+    Castling,
+    EnPassant,
+    HalfmoveClock,
+    FullmoveNumber,
+}
+
+impl Display for FenField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FenField::Placement => "piece placement",
+            FenField::ActiveColor => "active color",
+            FenField::Castling => "castling",
+            FenField::EnPassant => "en passant",
+            FenField::HalfmoveClock => "halfmove clock",
+            FenField::FullmoveNumber => "fullmove number",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// ranks are numbered like in the FEN itself, the first placement rank is rank 8
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FenError {
+    #[error("FEN is empty")]
+    Empty,
+    #[error("FEN has more than six fields")]
+    TooManyFields,
+    #[error("Piece placement has {0} ranks instead of 8")]
+    InvalidRankCount(usize),
+    #[error("Rank {rank} has {length} squares instead of 8")]
+    InvalidRankLength { rank: usize, length: usize },
+    #[error("Invalid piece {character:?} on rank {rank}")]
+    InvalidPiece { rank: usize, character: char },
+    #[error("Invalid character {character:?} in the {field} field")]
+    InvalidCharacter { field: FenField, character: char },
+    #[error("Invalid value {value:?} in the {field} field")]
+    InvalidValue { field: FenField, value: String },
+    #[error("{0:?} has no king")]
+    MissingKing(PieceColor),
+    #[error("{0:?} has more than one king")]
+    TooManyKings(PieceColor),
+    #[error("Pawn on back rank {rank} on the {file} file")]
+    PawnOnBackRank { rank: usize, file: char },
+    #[error("Castling right {0:?} has no king or rook on its starting square")]
+    CastlingWithoutPieces(char),
+    #[error("{0:?} king can be captured by the side to move")]
+    KingCapturable(PieceColor),
 }
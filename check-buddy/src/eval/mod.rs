@@ -0,0 +1,327 @@
+pub mod phase_score;
+mod piece_square_tables;
+
+use crate::bitboard::*;
+use crate::piece_color::PieceColor;
+use crate::piece_type::PieceType;
+use crate::position_move::Position;
+use crate::BoardMap;
+use phase_score::PhaseScore;
+use piece_square_tables::TABLES;
+use std::fmt::{Display, Formatter};
+
+/// phase with all minor and major pieces on the board
+pub const MAX_PHASE: i32 = 24;
+
+/// king, pawn, knight, bishop, rook, queen, indexed like the piece bitboards
+const PIECE_TYPES: [PieceType; 6] = [
+    PieceType::King,
+    PieceType::Pawn(false),
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Rook,
+    PieceType::Queen,
+];
+const PIECE_VALUES: [PhaseScore; 6] = [
+    PhaseScore::new(0, 0),
+    PhaseScore::new(100, 120),
+    PhaseScore::new(320, 300),
+    PhaseScore::new(330, 320),
+    PhaseScore::new(500, 520),
+    PhaseScore::new(900, 920),
+];
+/// how much each piece counts towards the phase
+const PHASE_WEIGHTS: [i32; 6] = [0, 0, 1, 1, 2, 4];
+
+const DOUBLED_PAWN: PhaseScore = PhaseScore::new(-10, -20);
+const ISOLATED_PAWN: PhaseScore = PhaseScore::new(-15, -20);
+/// by rank counted from the own side
+const PASSED_PAWN: [PhaseScore; 8] = [
+    PhaseScore::new(0, 0),
+    PhaseScore::new(5, 10),
+    PhaseScore::new(10, 20),
+    PhaseScore::new(20, 40),
+    PhaseScore::new(35, 60),
+    PhaseScore::new(60, 100),
+    PhaseScore::new(100, 150),
+    PhaseScore::new(0, 0),
+];
+/// per square a piece can move to, and the number of squares an average piece reaches
+const MOBILITY: [(PhaseScore, i32); 4] = [
+    (PhaseScore::new(4, 4), 4),
+    (PhaseScore::new(5, 5), 7),
+    (PhaseScore::new(2, 4), 7),
+    (PhaseScore::new(1, 2), 14),
+];
+/// per own pawn right in front of the king, and one rank further
+const PAWN_SHIELD: [PhaseScore; 2] = [PhaseScore::new(10, 0), PhaseScore::new(5, 0)];
+/// per square around the king attacked by a knight, bishop, rook or queen
+const KING_ATTACK_WEIGHTS: [i32; 4] = [2, 2, 3, 5];
+const KING_ATTACK: PhaseScore = PhaseScore::new(-4, 0);
+const BISHOP_PAIR: PhaseScore = PhaseScore::new(30, 50);
+
+const FILE_A: Bitboard = 0x0101_0101_0101_0101;
+
+/// score of the position in centipawns, positive when the side to move is better
+pub fn evaluate(board: &BoardMap) -> i32 {
+    Evaluation::new(board).score()
+}
+
+/// every term of the evaluation from white's point of view, before tapering
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Evaluation {
+    pub material: PhaseScore,
+    pub piece_squares: PhaseScore,
+    pub pawn_structure: PhaseScore,
+    pub mobility: PhaseScore,
+    pub king_safety: PhaseScore,
+    pub bishop_pair: PhaseScore,
+    /// from [`MAX_PHASE`] with all pieces on the board down to 0 with only kings and pawns
+    pub phase: i32,
+    pub active_color: PieceColor,
+}
+
+impl Evaluation {
+    pub fn new(board: &BoardMap) -> Self {
+        let term = |f: fn(&BoardMap, PieceColor) -> PhaseScore| {
+            f(board, PieceColor::White) - f(board, PieceColor::Black)
+        };
+        let phase = PIECE_TYPES
+            .iter()
+            .zip(PHASE_WEIGHTS)
+            .map(|(&piece_type, weight)| {
+                let count = board
+                    .get_bitboard(PieceColor::White, piece_type)
+                    .count_ones()
+                    + board
+                        .get_bitboard(PieceColor::Black, piece_type)
+                        .count_ones();
+                count as i32 * weight
+            })
+            .sum::<i32>()
+            .min(MAX_PHASE);
+        Self {
+            material: term(material),
+            piece_squares: term(piece_squares),
+            pawn_structure: term(pawn_structure),
+            mobility: term(mobility),
+            king_safety: term(king_safety),
+            bishop_pair: term(bishop_pair),
+            phase,
+            active_color: *board.get_active_color(),
+        }
+    }
+    /// the terms by name, in the order they are displayed
+    pub fn terms(&self) -> [(&'static str, PhaseScore); 6] {
+        [
+            ("material", self.material),
+            ("piece squares", self.piece_squares),
+            ("pawn structure", self.pawn_structure),
+            ("mobility", self.mobility),
+            ("king safety", self.king_safety),
+            ("bishop pair", self.bishop_pair),
+        ]
+    }
+    /// sum of all terms from white's point of view
+    pub fn total(&self) -> PhaseScore {
+        self.terms()
+            .iter()
+            .fold(PhaseScore::default(), |total, (_, score)| total + *score)
+    }
+    /// centipawns from white's point of view
+    pub fn white_score(&self) -> i32 {
+        self.total().taper(self.phase)
+    }
+    /// centipawns from the side to move's point of view
+    pub fn score(&self) -> i32 {
+        match self.active_color {
+            PieceColor::White => self.white_score(),
+            PieceColor::Black => -self.white_score(),
+        }
+    }
+}
+
+impl Display for Evaluation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:<16}{:>9}{:>9}{:>9}",
+            "term", "midgame", "endgame", "tapered"
+        )?;
+        for (name, score) in self.terms() {
+            writeln!(
+                f,
+                "{name:<16}{:>9}{:>9}{:>9}",
+                score.midgame,
+                score.endgame,
+                score.taper(self.phase)
+            )?;
+        }
+        let total = self.total();
+        writeln!(
+            f,
+            "{:<16}{:>9}{:>9}{:>9}",
+            "total",
+            total.midgame,
+            total.endgame,
+            self.white_score()
+        )?;
+        write!(
+            f,
+            "phase {}/{MAX_PHASE}, {} for the side to move",
+            self.phase,
+            self.score()
+        )
+    }
+}
+
+fn material(board: &BoardMap, color: PieceColor) -> PhaseScore {
+    PIECE_TYPES.iter().zip(PIECE_VALUES).fold(
+        PhaseScore::default(),
+        |score, (&piece_type, value)| {
+            score + value * board.get_bitboard(color, piece_type).count_ones() as i32
+        },
+    )
+}
+
+fn piece_squares(board: &BoardMap, color: PieceColor) -> PhaseScore {
+    let mut score = PhaseScore::default();
+    for (&piece_type, (midgame, endgame)) in PIECE_TYPES.iter().zip(TABLES) {
+        for position in positions(board.get_bitboard(color, piece_type)) {
+            let index = relative_index(position, color);
+            score += PhaseScore::new(midgame[index], endgame[index]);
+        }
+    }
+    score
+}
+
+fn pawn_structure(board: &BoardMap, color: PieceColor) -> PhaseScore {
+    let pawns = board.get_bitboard(color, PieceType::Pawn(false));
+    let enemy_pawns = board.get_bitboard(color.opposite(), PieceType::Pawn(false));
+    let mut score = PhaseScore::default();
+    for file in 0..8 {
+        let count = (pawns & file_mask(file)).count_ones() as i32;
+        if count > 1 {
+            score += DOUBLED_PAWN * (count - 1);
+        }
+    }
+    for position in positions(pawns) {
+        let file = position[1];
+        if pawns & adjacent_files(file) == EMPTY {
+            score += ISOLATED_PAWN;
+        }
+        let front_span = ahead(position[0], color) & (file_mask(file) | adjacent_files(file));
+        if enemy_pawns & front_span == EMPTY {
+            score += PASSED_PAWN[relative_rank(position, color)];
+        }
+    }
+    score
+}
+
+fn mobility(board: &BoardMap, color: PieceColor) -> PhaseScore {
+    let occupied = board.get_occupied();
+    // squares covered by an enemy pawn are not worth much to a piece
+    let safe = !board.get_color_bitboard(color) & !pawn_cover(board, color.opposite());
+    let mut score = PhaseScore::default();
+    for (&piece_type, (weight, average)) in PIECE_TYPES[2..].iter().zip(MOBILITY) {
+        for position in positions(board.get_bitboard(color, piece_type)) {
+            let moves = (attacks(piece_type, position, occupied) & safe).count_ones() as i32;
+            score += weight * (moves - average);
+        }
+    }
+    score
+}
+
+fn king_safety(board: &BoardMap, color: PieceColor) -> PhaseScore {
+    let Some(king) = positions(board.get_bitboard(color, PieceType::King)).next() else {
+        return PhaseScore::default();
+    };
+    let pawns = board.get_bitboard(color, PieceType::Pawn(false));
+    let files = file_mask(king[1]) | adjacent_files(king[1]);
+    let mut score = PhaseScore::default();
+    for (distance, bonus) in (1..=2).zip(PAWN_SHIELD) {
+        let row = match color {
+            PieceColor::White => king[0].checked_sub(distance),
+            PieceColor::Black => Some(king[0] + distance).filter(|&row| row < 8),
+        };
+        if let Some(row) = row {
+            score += bonus * (pawns & files & rank_mask(row)).count_ones() as i32;
+        }
+    }
+
+    let zone = king_attacks(king) | square_bit(king);
+    let occupied = board.get_occupied();
+    let enemy = color.opposite();
+    let mut units = 0;
+    for (&piece_type, weight) in PIECE_TYPES[2..].iter().zip(KING_ATTACK_WEIGHTS) {
+        for position in positions(board.get_bitboard(enemy, piece_type)) {
+            units += weight * (attacks(piece_type, position, occupied) & zone).count_ones() as i32;
+        }
+    }
+    score + KING_ATTACK * units
+}
+
+fn bishop_pair(board: &BoardMap, color: PieceColor) -> PhaseScore {
+    let bishops = board.get_bitboard(color, PieceType::Bishop);
+    if bishops & LIGHT_SQUARES != EMPTY && bishops & DARK_SQUARES != EMPTY {
+        BISHOP_PAIR
+    } else {
+        PhaseScore::default()
+    }
+}
+
+fn attacks(piece_type: PieceType, position: Position, occupied: Bitboard) -> Bitboard {
+    match piece_type {
+        PieceType::Knight => knight_attacks(position),
+        PieceType::Bishop => bishop_attacks(position, occupied),
+        PieceType::Rook => rook_attacks(position, occupied),
+        PieceType::Queen => queen_attacks(position, occupied),
+        PieceType::King => king_attacks(position),
+        PieceType::Pawn(_) => EMPTY,
+    }
+}
+
+fn pawn_cover(board: &BoardMap, color: PieceColor) -> Bitboard {
+    positions(board.get_bitboard(color, PieceType::Pawn(false))).fold(EMPTY, |cover, position| {
+        cover | pawn_attacks(position, color)
+    })
+}
+
+/// the square as seen by white, so both colors can share the tables
+fn relative_index(position: Position, color: PieceColor) -> usize {
+    match color {
+        PieceColor::White => square_index(position),
+        PieceColor::Black => square_index(position) ^ 56,
+    }
+}
+
+/// 0 on the color's back rank up to 7 on the promotion rank
+fn relative_rank(position: Position, color: PieceColor) -> usize {
+    match color {
+        PieceColor::White => 7 - position[0],
+        PieceColor::Black => position[0],
+    }
+}
+
+fn file_mask(file: usize) -> Bitboard {
+    FILE_A << file
+}
+
+fn rank_mask(row: usize) -> Bitboard {
+    0xff << (row * 8)
+}
+
+fn adjacent_files(file: usize) -> Bitboard {
+    let left = if file > 0 { file_mask(file - 1) } else { EMPTY };
+    let right = if file < 7 { file_mask(file + 1) } else { EMPTY };
+    left | right
+}
+
+/// every row in front of `row`, seen from the color's side
+fn ahead(row: usize, color: PieceColor) -> Bitboard {
+    match color {
+        PieceColor::White => (1 << (row * 8)) - 1,
+        PieceColor::Black if row == 7 => EMPTY,
+        PieceColor::Black => !((1 << ((row + 1) * 8)) - 1),
+    }
+}
@@ -0,0 +1,57 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// a score for the middlegame and one for the endgame, blended by the game phase
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PhaseScore {
+    pub midgame: i32,
+    pub endgame: i32,
+}
+
+impl PhaseScore {
+    pub const fn new(midgame: i32, endgame: i32) -> Self {
+        Self { midgame, endgame }
+    }
+    /// blends both scores, `phase` runs from 0 in a pawn ending to [`super::MAX_PHASE`]
+    pub fn taper(self, phase: i32) -> i32 {
+        let phase = phase.clamp(0, super::MAX_PHASE);
+        (self.midgame * phase + self.endgame * (super::MAX_PHASE - phase)) / super::MAX_PHASE
+    }
+}
+
+impl Add for PhaseScore {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        PhaseScore::new(self.midgame + rhs.midgame, self.endgame + rhs.endgame)
+    }
+}
+
+impl AddAssign for PhaseScore {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for PhaseScore {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for PhaseScore {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        PhaseScore::new(-self.midgame, -self.endgame)
+    }
+}
+
+impl Mul<i32> for PhaseScore {
+    type Output = Self;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        PhaseScore::new(self.midgame * rhs, self.endgame * rhs)
+    }
+}
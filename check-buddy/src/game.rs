@@ -1,21 +1,188 @@
 use crate::uci_move::UciMove;
 use crate::*;
+use anyhow::Result;
 use std::collections::HashMap;
 
+/// a game from its first position on, moves are made through it so the history,
+/// positions and result always match the board
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Game {
     pub info: HashMap<String, String>,
-    pub board_map: BoardMap,
-    pub historical_moves: Vec<UciMove>,
-    pub result: Option<bool>,
+    /// every position so far, the current one last
+    positions: Vec<BoardMap>,
+    historical_moves: Vec<UciMove>,
+    /// moves taken back by [`Game::undo`], the next one to redo last
+    undone_moves: Vec<UciMove>,
+    result: Option<Outcome>,
 }
 
 impl Default for Game {
     fn default() -> Self {
-        Self {
+        Self::new(BoardMap::starting())
+    }
+}
+
+impl Game {
+    /// a game starting from `board_map`
+    pub fn new(board_map: BoardMap) -> Self {
+        let mut game = Self {
             info: HashMap::with_capacity(12),
-            board_map: BoardMap::starting(),
+            positions: vec![board_map],
             historical_moves: vec![],
+            undone_moves: vec![],
             result: None,
+        };
+        game.update_result();
+        game
+    }
+    /// makes the move when it's legal and remembers it along with the position it leads to.
+    /// Moves taken back can't be redone after this
+    pub fn play(&mut self, uci_move: UciMove) -> Result<()> {
+        let mut board_map = *self.current();
+        let uci_move = board_map.with_board_context(uci_move);
+        board_map.single_move_turn(uci_move)?;
+        self.push(uci_move, board_map);
+        self.undone_moves.clear();
+        Ok(())
+    }
+    /// takes back the last move, returns None at the first position
+    pub fn undo(&mut self) -> Option<UciMove> {
+        let uci_move = self.historical_moves.pop()?;
+        self.positions.pop();
+        self.undone_moves.push(uci_move);
+        self.update_result();
+        Some(uci_move)
+    }
+    /// plays the last move taken back again, returns None when there is none
+    pub fn redo(&mut self) -> Option<UciMove> {
+        let uci_move = self.undone_moves.pop()?;
+        let mut board_map = *self.current();
+        board_map.make_move(uci_move);
+        self.push(uci_move, board_map);
+        Some(uci_move)
+    }
+    /// every move played so far
+    pub fn moves(&self) -> &[UciMove] {
+        &self.historical_moves
+    }
+    /// the position after `ply` moves, the first position at 0
+    pub fn position_at(&self, ply: usize) -> Option<&BoardMap> {
+        self.positions.get(ply)
+    }
+    /// the position after the last move
+    pub fn current(&self) -> &BoardMap {
+        self.positions.last().expect("a game has a first position")
+    }
+    /// how the game ended after the last move, None while it's ongoing
+    pub fn result(&self) -> Option<Outcome> {
+        self.result
+    }
+    /// checks if the game is over on the current board, or by repeating it three times
+    pub fn status(&self) -> Outcome {
+        let outcome = self.current().outcome();
+        if outcome == Outcome::Ongoing && self.is_threefold_repetition() {
+            return Outcome::ThreefoldRepetition;
+        }
+        outcome
+    }
+    /// zobrist key of every position so far, the current one last
+    pub fn position_keys(&self) -> Vec<u64> {
+        self.positions.iter().map(BoardMap::zobrist).collect()
+    }
+    /// halfmoves since the last capture or pawn move
+    pub fn halfmove_clock(&self) -> u32 {
+        self.current().get_halfmove_clock()
+    }
+    /// how often the current position occurred, itself included. Only positions since the
+    /// last capture or pawn move are looked at, as none before can be the same
+    pub fn repetitions(&self) -> usize {
+        let Some((current, earlier)) = self.positions.split_last() else {
+            return 0;
+        };
+        let repeated = earlier
+            .iter()
+            .rev()
+            .take(self.halfmove_clock() as usize)
+            .skip(1)
+            .step_by(2)
+            .filter(|position| position.zobrist() == current.zobrist())
+            .count();
+        repeated + 1
+    }
+    /// the current position occurred three times, so either side may claim a draw
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.repetitions() >= 3
+    }
+    /// the current position occurred five times, which ends the game in a draw
+    pub fn is_fivefold_repetition(&self) -> bool {
+        self.repetitions() >= 5
+    }
+    /// fifty moves by each side without a capture or pawn move, so either side may claim a draw
+    pub fn can_claim_fifty_moves(&self) -> bool {
+        self.halfmove_clock() >= 100
+    }
+    /// seventy-five moves by each side without a capture or pawn move end the game in a draw,
+    /// unless the last move gave checkmate
+    pub fn is_seventy_five_move_draw(&self) -> bool {
+        self.halfmove_clock() >= 150 && !matches!(self.current().outcome(), Outcome::Checkmate(_))
+    }
+
+    fn push(&mut self, uci_move: UciMove, board_map: BoardMap) {
+        self.historical_moves.push(uci_move);
+        self.positions.push(board_map);
+        self.update_result();
+    }
+
+    fn update_result(&mut self) {
+        let status = self.status();
+        self.result = status.is_over().then_some(status);
+    }
+}
+
+/// the fields of a written game, nothing in it is trusted but the first position and the moves
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct GameData {
+    info: HashMap<String, String>,
+    positions: Vec<BoardMap>,
+    historical_moves: Vec<UciMove>,
+    undone_moves: Vec<UciMove>,
+    /// read past for formats without field names, it's worked out again from the moves
+    #[serde(rename = "result")]
+    _result: Option<Outcome>,
+}
+
+/// played again from its first position, so a game read back can't have positions
+/// or a result that don't follow from its moves
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Game {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let GameData {
+            info,
+            positions,
+            historical_moves,
+            undone_moves,
+            ..
+        } = GameData::deserialize(deserializer)?;
+        let first = positions
+            .first()
+            .ok_or_else(|| D::Error::custom("a game needs a first position"))?;
+
+        let mut game = Game::new(*first);
+        game.info = info;
+        for (ply, &uci_move) in historical_moves
+            .iter()
+            .chain(undone_moves.iter().rev())
+            .enumerate()
+        {
+            game.play(uci_move)
+                .map_err(|e| D::Error::custom(format!("move {} {uci_move}: {e}", ply + 1)))?;
+        }
+        for _ in &undone_moves {
+            game.undo();
         }
+        Ok(game)
     }
 }
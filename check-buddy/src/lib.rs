@@ -10,4 +10,17 @@ pub use moves::*;
 mod game;
 pub use game::*;
 
+mod outcome;
+pub use outcome::*;
+
+mod eval;
+pub use eval::*;
+
+mod search;
+pub use search::*;
+
+mod transposition_table;
+pub use transposition_table::*;
+
 mod errors;
+pub use errors::*;
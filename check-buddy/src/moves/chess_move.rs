@@ -0,0 +1,114 @@
+use super::position_move::{notation_to_position, position_to_notation, Position};
+use crate::errors::UciMoveError;
+use crate::piece_type::PieceType;
+use crate::{BoardMap, Piece};
+use std::fmt::{Display, Formatter};
+
+/// the king moves two squares and takes the rook along
+pub const CASTLE: u8 = 1;
+/// a pawn takes the pawn beside it that just made a double step
+pub const EN_PASSANT: u8 = 2;
+/// a pawn moves two squares from its starting rank
+pub const DOUBLE_PUSH: u8 = 4;
+
+/// a move along with what the board it is made on says about it.
+/// [`Move::new`] only knows the squares, the board fills in the rest when the move is made
+///
+/// two moves are equal when they have the same squares and promotion,
+/// whether the board filled them in or not
+#[derive(Default, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Move {
+    pub from: Position,
+    pub to: Position,
+    /// the moving piece, an empty square until the board fills it in
+    pub piece: Piece,
+    /// the taken piece, the pawn beside `to` for en passant
+    pub captured: Piece,
+    /// piece a pawn turns into on the last rank, a queen when left empty
+    pub promotion: Option<PieceType>,
+    /// [`CASTLE`], [`EN_PASSANT`] and [`DOUBLE_PUSH`] combined
+    pub flags: u8,
+}
+
+impl Move {
+    pub fn new(from: Position, to: Position) -> Self {
+        Self {
+            from,
+            to,
+            ..Default::default()
+        }
+    }
+    pub fn with_promotion(mut self, piece_type: PieceType) -> Self {
+        self.promotion = Some(piece_type);
+        self
+    }
+    /// parses coordinate notation like `e2e4` or `e7e8q`, taking en passant and a missing
+    /// queen promotion from the board. Castling is written as the king's move, e.g. `e1g1`
+    pub fn from_uci(uci: &str, board: &BoardMap) -> Result<Self, UciMoveError> {
+        if !uci.is_ascii() || !(4..=5).contains(&uci.len()) {
+            return Err(UciMoveError::InvalidUciMove);
+        }
+        let from = notation_to_position(&uci[0..2]).ok_or(UciMoveError::FromNotFound)?;
+        let to = notation_to_position(&uci[2..4]).ok_or(UciMoveError::ToNotFound)?;
+        let mut position_move = Move::new(from, to);
+        if let Some(symbol) = uci[4..].chars().next() {
+            let piece_type = match symbol {
+                'q' => PieceType::Queen,
+                'r' => PieceType::Rook,
+                'b' => PieceType::Bishop,
+                'n' => PieceType::Knight,
+                _ => return Err(UciMoveError::InvalidPromotionPiece(symbol)),
+            };
+            position_move = position_move.with_promotion(piece_type);
+        }
+        Ok(board.with_board_context(position_move))
+    }
+    /// whether the board filled in the piece and flags yet
+    pub fn has_board_context(&self) -> bool {
+        self.piece.is_piece()
+    }
+    pub fn is_castle(&self) -> bool {
+        self.flags & CASTLE != 0
+    }
+    pub fn is_en_passant(&self) -> bool {
+        self.flags & EN_PASSANT != 0
+    }
+    pub fn is_double_push(&self) -> bool {
+        self.flags & DOUBLE_PUSH != 0
+    }
+    /// en passant included
+    pub fn is_capture(&self) -> bool {
+        self.captured.is_piece()
+    }
+    pub fn is_promotion(&self) -> bool {
+        self.promotion.is_some()
+    }
+}
+
+impl PartialEq for Move {
+    fn eq(&self, other: &Self) -> bool {
+        self.from == other.from && self.to == other.to && self.promotion == other.promotion
+    }
+}
+
+impl Eq for Move {}
+
+/// coordinate notation, e.g. `e2e4` or `e7e8q`
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let promotion = match self.promotion {
+            Some(PieceType::Queen) => "q",
+            Some(PieceType::Rook) => "r",
+            Some(PieceType::Bishop) => "b",
+            Some(PieceType::Knight) => "n",
+            _ => "",
+        };
+        write!(
+            f,
+            "{}{}{promotion}",
+            position_to_notation(self.from),
+            position_to_notation(self.to)
+        )
+    }
+}
@@ -1,2 +1,4 @@
+pub mod chess_move;
+pub mod move_list;
 pub mod position_move;
 pub mod uci_move;
@@ -0,0 +1,131 @@
+use super::chess_move::Move;
+use arrayvec::ArrayVec;
+use std::fmt::{Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// room for every move of any position, the most a legal position has is 218
+pub const MAX_MOVES: usize = 256;
+
+/// the moves of a position kept on the stack, so generating them doesn't allocate.
+/// Boards no game can reach may have more than [`MAX_MOVES`], those are moved to the heap.
+/// Derefs to a slice for everything beyond pushing
+#[derive(Clone, Default)]
+pub struct MoveList {
+    moves: ArrayVec<Move, MAX_MOVES>,
+    /// every move once there are too many for `moves`
+    overflow: Option<Vec<Move>>,
+}
+
+impl MoveList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn push(&mut self, position_move: Move) {
+        if let Some(overflow) = &mut self.overflow {
+            overflow.push(position_move);
+        } else if let Err(full) = self.moves.try_push(position_move) {
+            let mut overflow = Vec::with_capacity(MAX_MOVES * 2);
+            overflow.extend_from_slice(&self.moves);
+            overflow.push(full.element());
+            self.overflow = Some(overflow);
+        }
+    }
+    pub fn clear(&mut self) {
+        self.moves.clear();
+        self.overflow = None;
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &Self::Target {
+        self.overflow.as_deref().unwrap_or(&self.moves)
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.overflow {
+            Some(overflow) => overflow,
+            None => &mut self.moves,
+        }
+    }
+}
+
+impl Debug for MoveList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for MoveList {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Extend<Move> for MoveList {
+    fn extend<T: IntoIterator<Item = Move>>(&mut self, iter: T) {
+        for position_move in iter {
+            self.push(position_move);
+        }
+    }
+}
+
+impl FromIterator<Move> for MoveList {
+    fn from_iter<T: IntoIterator<Item = Move>>(iter: T) -> Self {
+        let mut moves = Self::new();
+        moves.extend(iter);
+        moves
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = IntoIter;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let overflow = self.overflow.take();
+        if overflow.is_some() {
+            self.moves.clear();
+        }
+        IntoIter {
+            moves: self.moves.into_iter(),
+            overflow: overflow.map(Vec::into_iter),
+        }
+    }
+}
+
+/// the moves of a [`MoveList`] by value
+pub struct IntoIter {
+    moves: arrayvec::IntoIter<Move, MAX_MOVES>,
+    overflow: Option<std::vec::IntoIter<Move>>,
+}
+
+impl Iterator for IntoIter {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.overflow {
+            Some(overflow) => overflow.next(),
+            None => self.moves.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.overflow {
+            Some(overflow) => overflow.size_hint(),
+            None => self.moves.size_hint(),
+        }
+    }
+}
@@ -1,23 +1,36 @@
-#[derive(Default, Clone, Copy, Debug)]
-pub struct PositionMove {
-    pub from: Position,
-    pub to: Position,
-    pub en_passant: bool,
-    pub promotion: bool,
+pub use super::chess_move::Move;
+use crate::piece_type::PieceType;
+
+/// the move type from before moves knew their piece and flags, see [`Move`]
+pub type PositionMove = Move;
+
+/// pieces a pawn can promote to, strongest first
+pub const PROMOTION_PIECES: [PieceType; 4] = [
+    PieceType::Queen,
+    PieceType::Rook,
+    PieceType::Bishop,
+    PieceType::Knight,
+];
+
+pub type Position = [usize; 2];
+
+/// converts a position to its square name, e.g. `[6, 4]` becomes `"e2"`
+pub fn position_to_notation(position: Position) -> String {
+    let file = (b'a' + position[1] as u8) as char;
+    let rank = (b'8' - position[0] as u8) as char;
+    format!("{file}{rank}")
 }
 
-impl PositionMove {
-    pub fn new(from: Position, to: Position) -> Self {
-        Self {
-            from,
-            to,
-            ..Default::default()
-        }
+/// converts a square name to a position, e.g. `"e2"` becomes `[6, 4]`
+pub fn notation_to_position(notation: &str) -> Option<Position> {
+    let mut chars = notation.chars();
+    let (file, rank) = (chars.next()?, chars.next()?);
+    if chars.next().is_some() || !('a'..='h').contains(&file) || !('1'..='8').contains(&rank) {
+        return None;
     }
+    Some([(b'8' - rank as u8) as usize, (file as u8 - b'a') as usize])
 }
 
-pub type Position = [usize; 2];
-
 pub enum Direction {
     North,
     East,
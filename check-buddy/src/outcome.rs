@@ -0,0 +1,32 @@
+use crate::piece_color::PieceColor;
+
+/// state of a game after the last move
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outcome {
+    Ongoing,
+    /// holds the winning color
+    Checkmate(PieceColor),
+    Stalemate,
+    InsufficientMaterial,
+    /// the automatic draw after seventy-five moves by each side without a capture or pawn move.
+    /// After fifty a draw can only be claimed, see [`crate::Game::can_claim_fifty_moves`]
+    FiftyMoveRule,
+    /// needs the game history, so only [`crate::Game::status`] can report it
+    ThreefoldRepetition,
+}
+
+impl Outcome {
+    pub fn is_over(&self) -> bool {
+        *self != Outcome::Ongoing
+    }
+    pub fn is_draw(&self) -> bool {
+        !matches!(self, Outcome::Ongoing | Outcome::Checkmate(_))
+    }
+    pub fn winner(&self) -> Option<PieceColor> {
+        match self {
+            Outcome::Checkmate(color) => Some(*color),
+            _ => None,
+        }
+    }
+}
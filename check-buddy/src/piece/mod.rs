@@ -5,7 +5,8 @@ use piece_color::*;
 use piece_type::*;
 use std::fmt::{Debug, Formatter};
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Piece(pub u32);
 
 impl Piece {
@@ -1,4 +1,5 @@
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PieceColor {
     Black,
     White,
@@ -11,4 +12,10 @@ impl PieceColor {
             PieceColor::White => 8,
         }
     }
+    pub fn opposite(self) -> Self {
+        match self {
+            PieceColor::Black => PieceColor::White,
+            PieceColor::White => PieceColor::Black,
+        }
+    }
 }
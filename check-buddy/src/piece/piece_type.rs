@@ -12,6 +12,7 @@ pub const WHITE: u32 = 8;
 pub const BLACK: u32 = 16;
 
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PieceType {
     Rook,
     Pawn(bool),
@@ -0,0 +1,342 @@
+mod move_ordering;
+mod pv_table;
+pub mod search_entry;
+pub mod search_limits;
+pub mod search_result;
+
+use crate::move_list::MoveList;
+use crate::{evaluate, BoardMap, TranspositionTable};
+use move_ordering::MoveOrdering;
+use pv_table::PvTable;
+use search_entry::{Bound, SearchEntry};
+use search_limits::SearchLimits;
+use search_result::SearchResult;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+pub const MAX_DEPTH: usize = 64;
+/// score of mating right now, a mate `n` plies away scores `MATE_SCORE - n`
+pub const MATE_SCORE: i32 = 1_000_000;
+/// transposition table size of a new [`Searcher`]
+pub const DEFAULT_HASH_SIZE_MB: usize = 16;
+
+/// picks moves with a negamax alpha-beta search, deepened one ply at a time,
+/// and a quiescence search over captures at the horizon
+#[derive(Debug)]
+pub struct Searcher {
+    limits: SearchLimits,
+    start: Instant,
+    nodes: u64,
+    stopped: bool,
+    stop: Arc<AtomicBool>,
+    move_ordering: MoveOrdering,
+    pv_table: PvTable,
+    /// the moves of every ply being searched, kept here instead of on the stack
+    /// so a deep search doesn't need a bigger one than a thread gets by default
+    move_lists: Box<[MoveList]>,
+    table: TranspositionTable<SearchEntry>,
+    /// keys of the positions played in the game, set by [`Searcher::set_history`]
+    game_history: Vec<u64>,
+    /// keys from the start of the game down to the node being searched
+    history: Vec<u64>,
+}
+
+impl Default for Searcher {
+    fn default() -> Self {
+        Self {
+            limits: SearchLimits::default(),
+            start: Instant::now(),
+            nodes: 0,
+            stopped: false,
+            stop: Arc::new(AtomicBool::new(false)),
+            move_ordering: MoveOrdering::default(),
+            pv_table: PvTable::default(),
+            move_lists: vec![MoveList::new(); MAX_DEPTH + 1].into_boxed_slice(),
+            table: TranspositionTable::new(DEFAULT_HASH_SIZE_MB),
+            game_history: vec![],
+            history: vec![],
+        }
+    }
+}
+
+impl Searcher {
+    pub fn new() -> Self {
+        Searcher::default()
+    }
+    /// resizes the transposition table, forgetting everything in it
+    pub fn set_hash_size(&mut self, size_mb: usize) {
+        self.table.resize(size_mb);
+    }
+    /// forgets all positions searched before, e.g. for a new game
+    pub fn clear_hash(&mut self) {
+        self.table.clear();
+    }
+    /// setting the flag from another thread stops the running search like a limit would.
+    /// It stays set until the caller clears it, so clear it before starting the next search
+    pub fn stop_flag(&self) -> Arc<AtomicBool> {
+        self.stop.clone()
+    }
+    /// keys of the positions played so far, oldest first, like [`crate::Game::position_keys`].
+    /// The search scores a move back into one of them as a draw.
+    /// The searched position may be left out, it's kept until the next call
+    pub fn set_history(&mut self, position_keys: &[u64]) {
+        self.game_history = position_keys.to_vec();
+    }
+    /// searches deeper until a limit is hit, an unfinished iteration is thrown away
+    ///
+    /// The moves of every ply are kept in the searcher, so the stack a thread gets
+    /// by default is enough for the deepest search
+    pub fn best_move(&mut self, board: &BoardMap, limits: SearchLimits) -> SearchResult {
+        self.search(board, limits, |_| {})
+    }
+    /// same as [`Searcher::best_move`], calling `on_iteration` with the result of every finished iteration
+    pub fn search(
+        &mut self,
+        board: &BoardMap,
+        limits: SearchLimits,
+        mut on_iteration: impl FnMut(&SearchResult),
+    ) -> SearchResult {
+        self.limits = limits;
+        self.start = Instant::now();
+        self.nodes = 0;
+        self.stopped = false;
+        self.move_ordering.clear();
+        self.table.new_search();
+
+        let mut board = *board;
+        self.history.clone_from(&self.game_history);
+        if self.history.last() != Some(&board.zobrist()) {
+            self.history.push(board.zobrist());
+        }
+        let mut result = SearchResult::default();
+        let max_depth = limits.depth.unwrap_or(MAX_DEPTH).clamp(1, MAX_DEPTH);
+        for depth in 1..=max_depth {
+            let score = self.negamax(&mut board, depth, 0, -MATE_SCORE, MATE_SCORE);
+            // the first iteration always counts, so there is a move even when time runs out
+            if self.stopped && depth > 1 {
+                break;
+            }
+            let pv = self.pv_table.line(0).to_vec();
+            result = SearchResult {
+                best_move: pv.first().copied(),
+                score,
+                depth,
+                nodes: self.nodes,
+                time: self.start.elapsed(),
+                hashfull: self.table.hashfull(),
+                pv,
+            };
+            on_iteration(&result);
+            if self.stopped || score.abs() >= MATE_SCORE - depth as i32 {
+                break;
+            }
+        }
+        result.nodes = self.nodes;
+        result.time = self.start.elapsed();
+        result
+    }
+
+    fn negamax(
+        &mut self,
+        board: &mut BoardMap,
+        depth: usize,
+        ply: usize,
+        alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        self.nodes += 1;
+        if self.nodes & 1023 == 0 {
+            self.check_limits();
+        }
+        self.pv_table.clear(ply);
+        if depth == 0 {
+            return self.quiescence(board, ply, alpha, beta);
+        }
+        board.gen_legal_moves(&mut self.move_lists[ply], false);
+        // the root always has to come up with a move, further down a draw ends the line.
+        // a checkmate on the hundredth halfmove still wins
+        if ply > 0
+            && !self.move_lists[ply].is_empty()
+            && (board.get_halfmove_clock() >= 100
+                || board.is_insufficient_material()
+                || self.is_repetition(board))
+        {
+            return 0;
+        }
+
+        let key = board.zobrist();
+        let entry = self.table.get(key).copied();
+        let hash_move = entry.and_then(|entry| entry.value.best_move);
+        if let Some(entry) = entry.filter(|entry| ply > 0 && entry.depth as usize >= depth) {
+            let score = score_from_table(entry.value.score, ply);
+            match entry.value.bound {
+                Bound::Exact => {
+                    self.pv_table.set(ply, hash_move);
+                    return score;
+                }
+                Bound::Lower if score >= beta => return score,
+                Bound::Upper if score <= alpha => return score,
+                _ => {}
+            }
+        }
+
+        self.move_ordering
+            .sort(board, &mut self.move_lists[ply], ply, hash_move);
+        let has_moves = !self.move_lists[ply].is_empty();
+        let score = self.search_moves(board, depth, ply, alpha, beta);
+        if has_moves && !self.stopped {
+            let bound = if score <= alpha {
+                Bound::Upper
+            } else if score >= beta {
+                Bound::Lower
+            } else {
+                Bound::Exact
+            };
+            let entry = SearchEntry {
+                best_move: self
+                    .pv_table
+                    .line(ply)
+                    .first()
+                    .copied()
+                    .filter(|_| bound != Bound::Upper),
+                score: score_to_table(score, ply),
+                bound,
+            };
+            self.table.insert(key, depth, entry);
+        }
+        score
+    }
+
+    /// only follows captures and promotions until the position is quiet, so the
+    /// evaluation never stops in the middle of an exchange. In check every move is tried
+    fn quiescence(&mut self, board: &mut BoardMap, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.nodes += 1;
+        if self.nodes & 1023 == 0 {
+            self.check_limits();
+        }
+        if ply >= MAX_DEPTH {
+            return evaluate(board);
+        }
+
+        let in_check = board.is_in_check(*board.get_active_color());
+        if !in_check {
+            // standing pat, the side to move doesn't have to take anything
+            let stand_pat = evaluate(board);
+            if stand_pat >= beta {
+                return beta;
+            }
+            alpha = alpha.max(stand_pat);
+        }
+        board.gen_legal_moves(&mut self.move_lists[ply], !in_check);
+        if in_check && self.move_lists[ply].is_empty() {
+            return -MATE_SCORE + ply as i32;
+        }
+        self.move_ordering
+            .sort(board, &mut self.move_lists[ply], ply, None);
+
+        // moves are copied out one at a time, the deeper plies use lists of their own
+        for index in 0..self.move_lists[ply].len() {
+            let position_move = self.move_lists[ply][index];
+            let undo = board.make_move(position_move);
+            let score = -self.quiescence(board, ply + 1, -beta, -alpha);
+            board.unmake_move(undo);
+
+            if self.stopped {
+                return alpha;
+            }
+            if score >= beta {
+                return beta;
+            }
+            alpha = alpha.max(score);
+        }
+        alpha
+    }
+
+    /// tries every move of the ply's list, scoring checkmate and stalemate when there are none
+    fn search_moves(
+        &mut self,
+        board: &mut BoardMap,
+        depth: usize,
+        ply: usize,
+        mut alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        if self.move_lists[ply].is_empty() {
+            return if board.is_in_check(*board.get_active_color()) {
+                -MATE_SCORE + ply as i32
+            } else {
+                0
+            };
+        }
+        for index in 0..self.move_lists[ply].len() {
+            let position_move = self.move_lists[ply][index];
+            let quiet = !position_move.is_capture() && !position_move.is_promotion();
+            let undo = board.make_move(position_move);
+            self.history.push(board.zobrist());
+            let score = -self.negamax(board, depth - 1, ply + 1, -beta, -alpha);
+            self.history.pop();
+            board.unmake_move(undo);
+
+            if self.stopped {
+                return alpha;
+            }
+            if score > alpha {
+                alpha = score;
+                self.pv_table.update(ply, position_move);
+                if alpha >= beta {
+                    if quiet {
+                        self.move_ordering
+                            .store_cutoff(board, position_move, depth, ply);
+                    }
+                    break;
+                }
+            }
+        }
+        alpha
+    }
+
+    /// the node's position occurred before, since the last capture or pawn move.
+    /// Once is enough, the side that repeats could repeat again
+    fn is_repetition(&self, board: &BoardMap) -> bool {
+        let Some((&key, earlier)) = self.history.split_last() else {
+            return false;
+        };
+        earlier
+            .iter()
+            .rev()
+            .take(board.get_halfmove_clock() as usize)
+            .skip(1)
+            .step_by(2)
+            .any(|&earlier_key| earlier_key == key)
+    }
+
+    fn check_limits(&mut self) {
+        let SearchLimits { nodes, time, .. } = self.limits;
+        self.stopped = self.stop.load(Ordering::Relaxed)
+            || nodes.is_some_and(|nodes| self.nodes >= nodes)
+            || time.is_some_and(|time| self.start.elapsed() >= time);
+    }
+}
+
+/// mate scores are stored counting from the position instead of from the root,
+/// so they stay right when the position is reached at another ply
+fn score_to_table(score: i32, ply: usize) -> i32 {
+    if score >= MATE_SCORE - MAX_DEPTH as i32 {
+        score + ply as i32
+    } else if score <= -MATE_SCORE + MAX_DEPTH as i32 {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+fn score_from_table(score: i32, ply: usize) -> i32 {
+    if score >= MATE_SCORE - MAX_DEPTH as i32 {
+        score - ply as i32
+    } else if score <= -MATE_SCORE + MAX_DEPTH as i32 {
+        score + ply as i32
+    } else {
+        score
+    }
+}
@@ -0,0 +1,140 @@
+use super::MAX_DEPTH;
+use crate::bitboard::{color_index, square_index};
+use crate::move_list::MAX_MOVES;
+use crate::piece_type::PieceType;
+use crate::position_move::PositionMove;
+use crate::BoardMap;
+
+const HASH_MOVE: i32 = i32::MAX;
+const CAPTURE: i32 = 2_000_000;
+const KILLER: i32 = 1_000_000;
+/// keeps the history scores of quiet moves below the killers
+const HISTORY_LIMIT: i32 = KILLER / 2;
+
+/// sorts the moves most likely to cause a cutoff first: the best move stored for the
+/// position, captures by MVV-LVA and promotions, then the killer moves of the ply, then quiet moves by history
+#[derive(Debug)]
+pub(crate) struct MoveOrdering {
+    /// two quiet moves per ply that caused a cutoff in a sibling node
+    killers: [[Option<PositionMove>; 2]; MAX_DEPTH],
+    /// cutoffs caused per color, from and to square
+    history: Box<[[[i32; 64]; 64]; 2]>,
+}
+
+impl Default for MoveOrdering {
+    fn default() -> Self {
+        Self {
+            killers: [[None; 2]; MAX_DEPTH],
+            history: Box::new([[[0; 64]; 64]; 2]),
+        }
+    }
+}
+
+impl MoveOrdering {
+    pub(crate) fn clear(&mut self) {
+        *self = MoveOrdering::default();
+    }
+
+    pub(crate) fn sort(
+        &self,
+        board: &BoardMap,
+        moves: &mut [PositionMove],
+        ply: usize,
+        hash_move: Option<PositionMove>,
+    ) {
+        // only boards no game can reach have more moves, the rest is left in generated order
+        let sorted_moves = moves.len().min(MAX_MOVES);
+        let moves = &mut moves[..sorted_moves];
+        let mut scores = [0; MAX_MOVES];
+        for (score, &position_move) in scores.iter_mut().zip(moves.iter()) {
+            *score = if Some(position_move) == hash_move {
+                HASH_MOVE
+            } else {
+                self.score(board, position_move, ply)
+            };
+        }
+        // an insertion sort keeps equal moves in the order they were generated, like a
+        // stable sort would, without the buffer those allocate
+        for sorted in 1..moves.len() {
+            let mut index = sorted;
+            while index > 0 && scores[index - 1] < scores[index] {
+                scores.swap(index - 1, index);
+                moves.swap(index - 1, index);
+                index -= 1;
+            }
+        }
+    }
+
+    /// remembers a quiet move that caused a beta cutoff
+    pub(crate) fn store_cutoff(
+        &mut self,
+        board: &BoardMap,
+        position_move: PositionMove,
+        depth: usize,
+        ply: usize,
+    ) {
+        if let Some(killers) = self.killers.get_mut(ply) {
+            if killers[0] != Some(position_move) {
+                killers[1] = killers[0];
+                killers[0] = Some(position_move);
+            }
+        }
+        let history = self.history_mut(board, position_move);
+        *history += (depth * depth) as i32;
+        if *history > HISTORY_LIMIT {
+            // halve everything so older cutoffs count less
+            self.history
+                .iter_mut()
+                .flatten()
+                .flatten()
+                .for_each(|score| *score /= 2);
+        }
+    }
+
+    fn score(&self, board: &BoardMap, position_move: PositionMove, ply: usize) -> i32 {
+        let PositionMove {
+            from,
+            to,
+            piece,
+            captured,
+            promotion,
+            ..
+        } = position_move;
+        let promotion_score = promotion.map_or(0, |piece_type| rank(piece_type) * 8);
+        if position_move.is_capture() {
+            // most valuable victim first, least valuable attacker breaking ties
+            let victim = captured.get_type().map_or(0, rank);
+            let attacker = piece.get_type().map_or(0, rank);
+            return CAPTURE + victim * 8 - attacker + promotion_score;
+        }
+        if promotion.is_some() {
+            return CAPTURE + promotion_score;
+        }
+        match self.killers.get(ply) {
+            Some([Some(killer), _]) if *killer == position_move => KILLER,
+            Some([_, Some(killer)]) if *killer == position_move => KILLER - 1,
+            _ => {
+                self.history[color_index(*board.get_active_color())][square_index(from)]
+                    [square_index(to)]
+            }
+        }
+    }
+
+    fn history_mut(&mut self, board: &BoardMap, position_move: PositionMove) -> &mut i32 {
+        let PositionMove { from, to, .. } = position_move;
+        &mut self.history[color_index(*board.get_active_color())][square_index(from)]
+            [square_index(to)]
+    }
+}
+
+/// piece order for MVV-LVA
+fn rank(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::Pawn(_) => 1,
+        PieceType::Knight => 2,
+        PieceType::Bishop => 3,
+        PieceType::Rook => 4,
+        PieceType::Queen => 5,
+        PieceType::King => 6,
+    }
+}
@@ -0,0 +1,51 @@
+use super::MAX_DEPTH;
+use crate::position_move::PositionMove;
+
+/// the best line found from every ply of the search, row `ply` holding the line
+/// from that ply on. Lines are copied up a row as they improve, so it never allocates
+#[derive(Debug)]
+pub(crate) struct PvTable {
+    lines: Box<[[PositionMove; MAX_DEPTH + 1]]>,
+    lengths: [usize; MAX_DEPTH + 1],
+}
+
+impl Default for PvTable {
+    fn default() -> Self {
+        Self {
+            // built on the heap, a boxed array would be put together on the stack first
+            lines: vec![[PositionMove::default(); MAX_DEPTH + 1]; MAX_DEPTH + 1].into_boxed_slice(),
+            lengths: [0; MAX_DEPTH + 1],
+        }
+    }
+}
+
+impl PvTable {
+    /// forgets the line of a node before it's searched
+    pub(crate) fn clear(&mut self, ply: usize) {
+        self.lengths[ply] = 0;
+    }
+
+    /// the line of `ply` becomes the move followed by the line of the next ply
+    pub(crate) fn update(&mut self, ply: usize, position_move: PositionMove) {
+        let child_length = self.lengths.get(ply + 1).copied().unwrap_or(0);
+        let (lines, child_lines) = self.lines.split_at_mut(ply + 1);
+        let line = &mut lines[ply];
+        line[0] = position_move;
+        if let Some(child_line) = child_lines.first() {
+            line[1..=child_length].copy_from_slice(&child_line[..child_length]);
+        }
+        self.lengths[ply] = child_length + 1;
+    }
+
+    /// a line of just the one move, if there is one
+    pub(crate) fn set(&mut self, ply: usize, position_move: Option<PositionMove>) {
+        if let Some(position_move) = position_move {
+            self.lines[ply][0] = position_move;
+        }
+        self.lengths[ply] = usize::from(position_move.is_some());
+    }
+
+    pub(crate) fn line(&self, ply: usize) -> &[PositionMove] {
+        &self.lines[ply][..self.lengths[ply]]
+    }
+}
@@ -0,0 +1,21 @@
+use crate::position_move::PositionMove;
+
+/// how the stored score relates to the real score of the position
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    Exact,
+    /// the search failed high, the real score is at least this
+    Lower,
+    /// the search failed low, the real score is at most this
+    Upper,
+}
+
+/// what the search keeps in its [`crate::TranspositionTable`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchEntry {
+    /// searched first when the position comes up again
+    pub best_move: Option<PositionMove>,
+    /// mate scores count from this position, not from the root
+    pub score: i32,
+    pub bound: Bound,
+}
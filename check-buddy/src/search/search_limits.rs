@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+/// when [`crate::Searcher::best_move`] has to stop deepening,
+/// without any limit it searches up to [`crate::MAX_DEPTH`]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchLimits {
+    pub depth: Option<usize>,
+    pub nodes: Option<u64>,
+    pub time: Option<Duration>,
+}
+
+impl SearchLimits {
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+    pub fn with_nodes(mut self, nodes: u64) -> Self {
+        self.nodes = Some(nodes);
+        self
+    }
+    pub fn with_time(mut self, time: Duration) -> Self {
+        self.time = Some(time);
+        self
+    }
+}
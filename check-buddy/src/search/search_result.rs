@@ -0,0 +1,30 @@
+use crate::position_move::PositionMove;
+use crate::{MATE_SCORE, MAX_DEPTH};
+use std::time::Duration;
+
+/// outcome of the deepest iteration the search finished
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct SearchResult {
+    /// empty when the side to move has no legal move
+    pub best_move: Option<PositionMove>,
+    /// centipawns from the side to move's point of view
+    pub score: i32,
+    pub depth: usize,
+    pub nodes: u64,
+    pub time: Duration,
+    /// how full the transposition table is in permille
+    pub hashfull: usize,
+    /// principal variation, the best line for both sides starting with `best_move`
+    pub pv: Vec<PositionMove>,
+}
+
+impl SearchResult {
+    /// moves until mate when the score is a mate score, negative when the side to move gets mated
+    pub fn mate_in(&self) -> Option<i32> {
+        if self.score.abs() < MATE_SCORE - MAX_DEPTH as i32 {
+            return None;
+        }
+        let moves = (MATE_SCORE - self.score.abs() + 1) / 2;
+        Some(moves * self.score.signum())
+    }
+}
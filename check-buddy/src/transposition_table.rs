@@ -0,0 +1,87 @@
+use std::mem::size_of;
+
+/// fixed-size hash table of positions keyed by [`crate::BoardMap::zobrist`]
+///
+/// every key has a single slot. A new entry only replaces one that was searched
+/// at least as deep, unless the old entry is left over from an earlier search
+#[derive(Clone, Debug)]
+pub struct TranspositionTable<T> {
+    entries: Vec<Option<TableEntry<T>>>,
+    generation: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TableEntry<T> {
+    pub key: u64,
+    pub depth: u8,
+    generation: u8,
+    pub value: T,
+}
+
+impl<T: Copy> TranspositionTable<T> {
+    /// a table taking up to `size_mb` megabytes, but always holding at least one entry
+    pub fn new(size_mb: usize) -> Self {
+        Self {
+            entries: vec![None; Self::capacity_for(size_mb)],
+            generation: 0,
+        }
+    }
+    /// changes the size, which drops every entry
+    pub fn resize(&mut self, size_mb: usize) {
+        *self = TranspositionTable::new(size_mb);
+    }
+    pub fn clear(&mut self) {
+        self.entries.fill(None);
+    }
+    /// marks all current entries as old, so any new entry may replace them
+    pub fn new_search(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+    /// number of entries the table can hold
+    pub fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn get(&self, key: u64) -> Option<&TableEntry<T>> {
+        self.entries[self.index(key)]
+            .as_ref()
+            .filter(|entry| entry.key == key)
+    }
+    pub fn insert(&mut self, key: u64, depth: usize, value: T) {
+        let generation = self.generation;
+        let depth = depth.min(u8::MAX as usize) as u8;
+        let index = self.index(key);
+        let slot = &mut self.entries[index];
+        let replace = match slot {
+            Some(entry) => {
+                entry.key == key || entry.generation != generation || depth >= entry.depth
+            }
+            None => true,
+        };
+        if replace {
+            *slot = Some(TableEntry {
+                key,
+                depth,
+                generation,
+                value,
+            });
+        }
+    }
+    /// how full the table is in permille, sampled from the first thousand slots
+    pub fn hashfull(&self) -> usize {
+        let sample = self.entries.len().min(1000);
+        let used = self.entries[..sample]
+            .iter()
+            .flatten()
+            .filter(|entry| entry.generation == self.generation)
+            .count();
+        used * 1000 / sample
+    }
+
+    fn index(&self, key: u64) -> usize {
+        (key % self.entries.len() as u64) as usize
+    }
+
+    fn capacity_for(size_mb: usize) -> usize {
+        (size_mb * 1024 * 1024 / size_of::<Option<TableEntry<T>>>()).max(1)
+    }
+}
@@ -0,0 +1,69 @@
+use check_buddy::search_limits::SearchLimits;
+use check_buddy::{BoardMap, Searcher};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+/// counts the allocations of each thread, so tests running alongside don't get in the way
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let value = f();
+    (value, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn move_generation_should_not_allocate() {
+    let board = BoardMap::from_fen(KIWIPETE_FEN);
+
+    let (moves, count) = allocations(|| board.legal_moves());
+    assert_eq!(48, moves.len());
+    assert_eq!(0, count);
+    let (captures, count) = allocations(|| board.legal_captures());
+    assert_eq!(8, captures.len());
+    assert_eq!(0, count);
+    let (pieces, count) = allocations(|| board.pieces(*board.get_active_color()).count());
+    assert_eq!(16, pieces);
+    assert_eq!(0, count);
+}
+
+#[test]
+fn perft_should_not_allocate() {
+    let board = BoardMap::from_fen(KIWIPETE_FEN);
+
+    let (nodes, count) = allocations(|| board.perft(3));
+    assert_eq!(97_862, nodes);
+    assert_eq!(0, count);
+}
+
+#[test]
+fn search_should_not_allocate_per_node() {
+    let board = BoardMap::from_fen(KIWIPETE_FEN);
+    let mut searcher = Searcher::new();
+
+    // only the search setup and the result of each iteration allocate
+    let (result, count) =
+        allocations(|| searcher.best_move(&board, SearchLimits::default().with_depth(4)));
+    assert!(result.nodes > 10_000, "{}", result.nodes);
+    assert!(count < 32, "{count} allocations");
+}
@@ -0,0 +1,91 @@
+use check_buddy::piece_color::PieceColor;
+use check_buddy::BoardMap;
+
+#[test]
+fn is_in_check_should_find_checks_by_every_piece_type() {
+    for fen in [
+        "4k3/8/8/8/8/8/3p4/4K3 w - - 0 1",
+        "4k3/8/8/8/8/5n2/8/4K3 w - - 0 1",
+        "4k3/8/8/8/1b6/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/4r3/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/q3K3 w - - 0 1",
+    ] {
+        let board = BoardMap::from_fen(fen);
+        assert!(board.is_in_check(PieceColor::White), "{fen}");
+        assert!(!board.is_in_check(PieceColor::Black), "{fen}");
+    }
+}
+
+#[test]
+fn blocked_lines_should_not_give_check() {
+    let board = BoardMap::from_fen("4k3/8/8/8/4r3/8/4N3/4K3 w - - 0 1");
+
+    assert!(!board.is_in_check(PieceColor::White));
+}
+
+#[test]
+fn pawns_should_only_attack_forward() {
+    let board = BoardMap::from_fen("4k3/8/8/3p4/8/8/8/4K3 w - - 0 1");
+
+    assert!(board.is_square_attacked([4, 2], PieceColor::Black));
+    assert!(board.is_square_attacked([4, 4], PieceColor::Black));
+    assert!(!board.is_square_attacked([2, 2], PieceColor::Black));
+    assert!(!board.is_square_attacked([4, 3], PieceColor::Black));
+}
+
+#[test]
+fn attackers_of_should_list_every_attacker() {
+    // e5 is attacked by the d4 pawn, f3 knight, h2 bishop, e1 rook and the h5 queen
+    let board = BoardMap::from_fen("4k3/8/8/7Q/3P4/5N2/7B/4R1K1 w - - 0 1");
+    let mut attackers = board.attackers_of([3, 4], PieceColor::White);
+    attackers.sort();
+
+    assert_eq!(vec![[3, 7], [4, 3], [5, 5], [6, 7], [7, 4]], attackers);
+    assert!(board.attackers_of([3, 4], PieceColor::Black).is_empty());
+}
+
+#[test]
+fn pinned_pieces_should_still_attack() {
+    // the e2 knight is pinned against the king but still guards d4
+    let board = BoardMap::from_fen("4r1k1/8/8/8/8/8/4N3/4K3 w - - 0 1");
+
+    assert!(board.is_square_attacked([4, 3], PieceColor::White));
+    assert!(board.gen_legal_positions([6, 4]).is_empty());
+}
+
+#[test]
+fn pinned_pieces_should_move_along_the_pin() {
+    // the e4 rook is pinned by the e8 rook and the b4 bishop by the a5 queen
+    let board = BoardMap::from_fen("4r1k1/8/8/q7/1B2R3/8/8/4K3 w - - 0 1");
+
+    let mut rook = board.gen_legal_positions([4, 4]);
+    rook.sort();
+    assert_eq!(vec![[0, 4], [1, 4], [2, 4], [3, 4], [5, 4], [6, 4]], rook);
+    let mut bishop = board.gen_legal_positions([4, 1]);
+    bishop.sort();
+    assert_eq!(vec![[3, 0], [5, 2], [6, 3]], bishop);
+}
+
+#[test]
+fn double_check_should_leave_only_king_moves() {
+    // the e8 rook and the b4 bishop both give check, the knight could block either one
+    let board = BoardMap::from_fen("4r1k1/8/8/8/1b6/5N2/8/4K2R w K - 0 1");
+
+    assert!(board
+        .gen_all_legal_moves()
+        .iter()
+        .all(|position_move| position_move.from == [7, 4]));
+    assert_eq!(3, board.gen_all_legal_moves().len());
+    assert!(board.gen_legal_positions([5, 5]).is_empty());
+}
+
+#[test]
+fn en_passant_should_not_uncover_the_king_along_the_row() {
+    // taking d6 would take both pawns off the fifth rank, in the h5 rook's way
+    let board = BoardMap::from_fen("4k3/8/8/K2pP2r/8/8/8/8 w - d6 0 1");
+    assert_eq!(vec![[2, 4]], board.gen_legal_positions([3, 4]));
+
+    // taking the pawn that just gave check is fine
+    let board = BoardMap::from_fen("8/8/8/2k5/3Pp3/8/8/4K3 b - d3 0 1");
+    assert!(board.gen_legal_positions([4, 4]).contains(&[5, 3]));
+}
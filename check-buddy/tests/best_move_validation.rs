@@ -1,7 +1,8 @@
 use anyhow::{anyhow, Error, Result};
 use calamine::*;
 use check_buddy::position_move::Position;
-use check_buddy::BoardMap;
+use check_buddy::search_limits::SearchLimits;
+use check_buddy::{BoardMap, Outcome, Searcher, MATE_SCORE};
 use std::ops::Sub;
 
 #[test]
@@ -36,6 +37,40 @@ fn best_move_should_be_valid() {
     });
 }
 
+/// the dataset holds mates in one, which a search of two plies has to see. Any move that
+/// mates will do, a position can have more than the one the dataset gives
+#[test]
+fn searcher_should_find_a_mate_in_one() {
+    let move_data = gen_move_data().unwrap();
+    let mut searcher = Searcher::new();
+    for (board_map, _) in &move_data {
+        let result = searcher.best_move(board_map, SearchLimits::default().with_depth(2));
+        let best_move = result
+            .best_move
+            .unwrap_or_else(|| panic!("no move in {}", board_map.get_fen()));
+        assert!(
+            board_map.gen_all_legal_moves().contains(&best_move),
+            "{best_move} isn't legal in {}",
+            board_map.get_fen()
+        );
+        assert_eq!(
+            MATE_SCORE - 1,
+            result.score,
+            "{best_move} in {}",
+            board_map.get_fen()
+        );
+
+        let mut board = *board_map;
+        board.make_move(best_move);
+        assert_eq!(
+            Outcome::Checkmate(*board_map.get_active_color()),
+            board.outcome(),
+            "{best_move} doesn't mate in {}",
+            board_map.get_fen()
+        );
+    }
+}
+
 fn gen_move_data() -> Result<Vec<(BoardMap, (Position, Position))>> {
     let path = format!(
         "{}/tests/datasets/best_move.xls",
@@ -0,0 +1,114 @@
+use check_buddy::bitboard::{self, square_bit, EMPTY};
+use check_buddy::piece_color::PieceColor;
+use check_buddy::piece_type::{PieceType, ROOK, WHITE};
+use check_buddy::position_move::PositionMove;
+use check_buddy::{BoardMap, Piece};
+
+#[test]
+fn bitboards_should_follow_the_squares() {
+    let mut board =
+        BoardMap::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+    for position_move in [
+        PositionMove::new([7, 4], [7, 6]),
+        PositionMove::new([1, 2], [3, 2]),
+        PositionMove::new([3, 3], [2, 2]),
+        PositionMove::new([4, 1], [5, 2]),
+    ] {
+        board.single_move_turn(position_move).unwrap();
+    }
+
+    let mut occupied = EMPTY;
+    for row in 0..8 {
+        for file in 0..8 {
+            let piece = board.get_piece([row, file]);
+            if let Some(piece_type) = piece.get_type() {
+                assert_ne!(
+                    EMPTY,
+                    board.get_bitboard(piece.get_color(), piece_type) & square_bit([row, file])
+                );
+                occupied |= square_bit([row, file]);
+            }
+        }
+    }
+    assert_eq!(occupied, board.get_occupied());
+    assert_eq!(
+        board.get_occupied(),
+        board.get_color_bitboard(PieceColor::White) | board.get_color_bitboard(PieceColor::Black)
+    );
+}
+
+#[test]
+fn writing_squares_should_keep_the_bitboards_in_sync() {
+    let expected = BoardMap::from_fen("4k3/8/8/8/8/8/8/R3K2R w - - 0 1");
+
+    let mut board = BoardMap::from_fen("4k3/8/8/8/8/8/8/4K2R w - - 0 1");
+    board.get_piece_mut([7, 0]).0 = ROOK | WHITE;
+    assert_eq!(
+        expected.get_bitboard(PieceColor::White, PieceType::Rook),
+        board.get_bitboard(PieceColor::White, PieceType::Rook)
+    );
+    assert_eq!(expected.zobrist(), board.zobrist());
+
+    let mut board = BoardMap::from_fen("4k3/8/8/8/8/8/8/4K2R w - - 0 1");
+    {
+        let mut squares = board.squares_mut();
+        squares[7][0] = Piece(ROOK | WHITE);
+    }
+    assert_eq!(expected.get_occupied(), board.get_occupied());
+    assert_eq!(expected.zobrist(), board.zobrist());
+    assert_eq!(expected.get_fen(), board.get_fen());
+}
+
+#[test]
+fn sliding_attacks_should_stop_at_the_first_piece() {
+    // rook on d4, blocked on d6 and f4
+    let occupied = square_bit([4, 3]) | square_bit([2, 3]) | square_bit([4, 5]);
+    let attacks = bitboard::rook_attacks([4, 3], occupied);
+
+    assert_ne!(EMPTY, attacks & square_bit([2, 3]));
+    assert_eq!(EMPTY, attacks & square_bit([1, 3]));
+    assert_ne!(EMPTY, attacks & square_bit([4, 5]));
+    assert_eq!(EMPTY, attacks & square_bit([4, 6]));
+    assert_ne!(EMPTY, attacks & square_bit([7, 3]));
+    assert_ne!(EMPTY, attacks & square_bit([4, 0]));
+    assert_eq!(10, attacks.count_ones());
+}
+
+#[test]
+fn between_should_only_hold_squares_on_a_shared_line() {
+    assert_eq!(
+        square_bit([6, 1]) | square_bit([5, 2]),
+        bitboard::between([7, 0], [4, 3])
+    );
+    assert_eq!(EMPTY, bitboard::between([7, 0], [5, 1]));
+    assert_eq!(EMPTY, bitboard::between([7, 0], [6, 0]));
+}
+
+#[test]
+fn line_should_run_from_edge_to_edge() {
+    let diagonal = (0..8).fold(EMPTY, |line, i| line | square_bit([7 - i, i]));
+    assert_eq!(diagonal, bitboard::line([5, 2], [3, 4]));
+    assert_eq!(diagonal, bitboard::line([3, 4], [5, 2]));
+    assert_eq!(0xff << 16, bitboard::line([2, 6], [2, 1]));
+    assert_eq!(EMPTY, bitboard::line([7, 0], [5, 1]));
+}
+
+#[test]
+fn find_piece_should_read_the_bitboards() {
+    let board = BoardMap::starting();
+
+    assert_eq!(
+        vec![
+            [6, 0],
+            [6, 1],
+            [6, 2],
+            [6, 3],
+            [6, 4],
+            [6, 5],
+            [6, 6],
+            [6, 7]
+        ],
+        board.find_piece(PieceColor::White, PieceType::Pawn(false))
+    );
+    assert_eq!(16, board.get_active_pieces().len());
+}
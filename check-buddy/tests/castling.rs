@@ -0,0 +1,111 @@
+use check_buddy::piece_type::{KING, ROOK, WHITE};
+use check_buddy::position_move::PositionMove;
+use check_buddy::{BoardMap, Piece};
+
+const CASTLING_FEN: &str = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+
+#[test]
+fn king_move_of_two_squares_should_castle() {
+    let mut board = BoardMap::from_fen(CASTLING_FEN);
+    board
+        .single_move_turn(PositionMove::new([7, 4], [7, 6]))
+        .unwrap();
+
+    assert_eq!(Piece(KING | WHITE), board.get_piece([7, 6]));
+    assert_eq!(Piece(ROOK | WHITE), board.get_piece([7, 5]));
+    assert!(!board.get_piece([7, 7]).is_piece());
+    assert_eq!("r3k2r/8/8/8/8/8/8/R4RK1 b kq - 1 1", board.get_fen());
+
+    board
+        .single_move_turn(PositionMove::new([0, 4], [0, 2]))
+        .unwrap();
+    assert_eq!("2kr3r/8/8/8/8/8/8/R4RK1 w - - 2 2", board.get_fen());
+}
+
+#[test]
+fn uci_castling_should_move_rook_and_remove_rights() {
+    let mut board = BoardMap::from_fen(CASTLING_FEN);
+    let castle = board.parse_uci_to_move("O-O-O").unwrap();
+    board.uci_move_turn(castle).unwrap();
+
+    assert_eq!("r3k2r/8/8/8/8/8/8/2KR3R b kq - 1 1", board.get_fen());
+}
+
+#[test]
+fn rook_moves_should_remove_one_right() {
+    let mut board = BoardMap::from_fen(CASTLING_FEN);
+    board
+        .single_move_turn(PositionMove::new([7, 0], [6, 0]))
+        .unwrap();
+    assert_eq!("Kkq", board.get_castling_rights().to_string());
+
+    board
+        .single_move_turn(PositionMove::new([0, 7], [1, 7]))
+        .unwrap();
+    assert_eq!("Kq", board.get_castling_rights().to_string());
+}
+
+#[test]
+fn capturing_a_rook_should_remove_its_right() {
+    let mut board = BoardMap::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    board
+        .single_move_turn(PositionMove::new([7, 7], [0, 7]))
+        .unwrap();
+
+    assert_eq!("Qq", board.get_castling_rights().to_string());
+}
+
+#[test]
+fn king_moving_back_should_not_regain_rights() {
+    let mut board = BoardMap::from_fen(CASTLING_FEN);
+    for piece_move in [
+        PositionMove::new([7, 4], [7, 3]),
+        PositionMove::new([0, 4], [0, 3]),
+        PositionMove::new([7, 3], [7, 4]),
+        PositionMove::new([0, 3], [0, 4]),
+    ] {
+        board.single_move_turn(piece_move).unwrap();
+    }
+
+    assert!(board.get_castling_rights().is_empty());
+    let positions = board.gen_legal_positions([7, 4]);
+    assert!(!positions.contains(&[7, 6]));
+    assert!(!positions.contains(&[7, 2]));
+}
+
+#[test]
+fn castling_should_be_refused_when_in_check() {
+    let board = BoardMap::from_fen("r3k2r/8/8/8/8/8/4r3/R3K2R w KQkq - 0 1");
+    let positions = board.gen_legal_positions([7, 4]);
+
+    assert!(!positions.contains(&[7, 6]));
+    assert!(!positions.contains(&[7, 2]));
+}
+
+#[test]
+fn castling_should_be_refused_through_attacked_squares() {
+    // f1 and d1 are attacked
+    let board = BoardMap::from_fen("r3k2r/8/8/8/8/8/3r1r2/R3K2R w KQkq - 0 1");
+    let positions = board.gen_legal_positions([7, 4]);
+
+    assert!(!positions.contains(&[7, 6]));
+    assert!(!positions.contains(&[7, 2]));
+}
+
+#[test]
+fn castling_should_be_refused_into_check() {
+    let board = BoardMap::from_fen("r3k2r/8/8/8/8/8/2r3r1/R3K2R w KQkq - 0 1");
+    let positions = board.gen_legal_positions([7, 4]);
+
+    assert!(!positions.contains(&[7, 6]));
+    assert!(!positions.contains(&[7, 2]));
+}
+
+#[test]
+fn long_castling_should_allow_an_attacked_rook_path() {
+    // only the king's path has to be safe, b1 may be attacked
+    let board = BoardMap::from_fen("r3k2r/8/8/8/8/8/1r6/R3K2R w KQkq - 0 1");
+    let positions = board.gen_legal_positions([7, 4]);
+
+    assert!(positions.contains(&[7, 2]));
+}
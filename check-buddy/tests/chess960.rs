@@ -0,0 +1,107 @@
+use check_buddy::piece_color::PieceColor;
+use check_buddy::piece_type::{KING, ROOK, WHITE};
+use check_buddy::position_move::PositionMove;
+use check_buddy::{BoardMap, Piece};
+
+const CHESS960_FEN: &str = "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9";
+
+#[test]
+fn starting_positions_should_follow_the_standard_numbering() {
+    assert_eq!(
+        "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w HFhf - 0 1",
+        BoardMap::chess960_starting(0).unwrap().get_fen()
+    );
+    assert_eq!(
+        "rkrnnqbb/pppppppp/8/8/8/8/PPPPPPPP/RKRNNQBB w CAca - 0 1",
+        BoardMap::chess960_starting(959).unwrap().get_fen()
+    );
+    assert!(BoardMap::chess960_starting(960).is_none());
+}
+
+#[test]
+fn position_518_should_be_the_standard_one() {
+    let board = BoardMap::chess960_starting(518).unwrap();
+    assert!(board.is_chess960());
+    assert_eq!(BoardMap::starting().zobrist(), board.zobrist());
+    assert_eq!(20, board.gen_all_legal_moves().len());
+}
+
+#[test]
+fn every_starting_position_should_have_its_king_between_the_rooks() {
+    for index in 0..960 {
+        let board = BoardMap::chess960_starting(index).unwrap();
+        let rights = board.get_castling_rights();
+        let fen = board.get_fen();
+        let back_rank = fen.split('/').next().unwrap();
+        let king = back_rank.find('k').unwrap();
+        assert!(rights.rook_file(PieceColor::Black, false) < king, "{fen}");
+        assert!(king < rights.rook_file(PieceColor::Black, true), "{fen}");
+        assert_eq!(fen, BoardMap::from_fen(&fen).get_fen());
+    }
+}
+
+#[test]
+fn shredder_and_x_fen_should_give_the_same_rights() {
+    let shredder = BoardMap::from_fen(CHESS960_FEN);
+    let x_fen = BoardMap::from_fen(CHESS960_FEN.replace("HFhf", "KQkq"));
+
+    assert!(shredder.is_chess960());
+    assert!(x_fen.is_chess960());
+    assert_eq!(shredder.get_castling_rights(), x_fen.get_castling_rights());
+    assert_eq!(CHESS960_FEN, x_fen.get_fen());
+    assert_eq!(
+        5,
+        shredder
+            .get_castling_rights()
+            .rook_file(PieceColor::White, false)
+    );
+}
+
+#[test]
+fn standard_positions_should_stay_standard() {
+    let board = BoardMap::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    assert!(!board.is_chess960());
+    assert_eq!("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", board.get_fen());
+}
+
+#[test]
+fn castling_should_be_the_king_taking_its_rook() {
+    let mut board = BoardMap::from_fen("4k3/8/8/8/8/8/8/1R3KR1 w GB - 0 1");
+    assert!(board
+        .single_move_turn(PositionMove::new([7, 5], [7, 6]))
+        .is_ok());
+    assert_eq!(Piece(KING | WHITE), board.get_piece([7, 6]));
+    assert_eq!(Piece(ROOK | WHITE), board.get_piece([7, 5]));
+    assert_eq!("4k3/8/8/8/8/8/8/1R3RK1 b - - 1 1", board.get_fen());
+
+    let mut board = BoardMap::from_fen("4k3/8/8/8/8/8/8/1R3KR1 w GB - 0 1");
+    board
+        .single_move_turn(PositionMove::new([7, 5], [7, 1]))
+        .unwrap();
+    assert_eq!(Piece(KING | WHITE), board.get_piece([7, 2]));
+    assert_eq!(Piece(ROOK | WHITE), board.get_piece([7, 3]));
+    assert_eq!("4k3/8/8/8/8/8/8/2KR2R1 b - - 1 1", board.get_fen());
+}
+
+#[test]
+fn castling_should_unmake_back_to_the_same_board() {
+    let board = BoardMap::from_fen("4k3/8/8/8/8/8/8/RK5R w HA - 0 1");
+    for short in ["O-O", "O-O-O"] {
+        let mut copy = board;
+        let castle = copy.parse_uci_to_move(short).unwrap();
+        assert!(castle.is_castle());
+        let undo = copy.make_move(castle);
+        copy.unmake_move(undo);
+        assert_eq!(board, copy);
+    }
+}
+
+#[test]
+fn san_should_write_castling_as_usual() {
+    let board = BoardMap::from_fen("4k3/8/8/8/8/8/8/RK5R w HA - 0 1");
+    let short = board.parse_uci_to_move("O-O").unwrap();
+    assert_eq!("O-O", board.to_san(short));
+    let long = board.parse_uci_to_move("O-O-O").unwrap();
+    assert_eq!("O-O-O", board.to_san(long));
+    assert_eq!(long, PositionMove::from_uci("b1a1", &board).unwrap());
+}
@@ -0,0 +1,154 @@
+use check_buddy::phase_score::PhaseScore;
+use check_buddy::{evaluate, BoardMap, Evaluation, MAX_PHASE};
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+#[test]
+fn starting_position_should_be_even() {
+    let evaluation = Evaluation::new(&BoardMap::starting());
+
+    assert_eq!(evaluation.total(), PhaseScore::default());
+    assert_eq!(evaluation.phase, MAX_PHASE);
+    assert_eq!(evaluate(&BoardMap::starting()), 0);
+}
+
+#[test]
+fn mirrored_positions_should_score_the_same() {
+    for fen in [
+        KIWIPETE_FEN,
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3",
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+    ] {
+        let board = BoardMap::from_fen(fen);
+        let mirrored = BoardMap::from_fen(mirror(fen));
+
+        assert_eq!(evaluate(&board), evaluate(&mirrored), "{fen}");
+        assert_eq!(
+            Evaluation::new(&board).white_score(),
+            -Evaluation::new(&mirrored).white_score(),
+            "{fen}"
+        );
+    }
+}
+
+#[test]
+fn score_should_be_from_the_side_to_move() {
+    let white = BoardMap::from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1");
+    let black = BoardMap::from_fen("4k3/8/8/8/8/8/8/3QK3 b - - 0 1");
+
+    assert!(evaluate(&white) > 800);
+    assert_eq!(evaluate(&white), -evaluate(&black));
+}
+
+#[test]
+fn terms_should_add_up_to_the_score() {
+    let evaluation = Evaluation::new(&BoardMap::from_fen(KIWIPETE_FEN));
+    let total = evaluation
+        .terms()
+        .iter()
+        .fold(PhaseScore::default(), |total, (_, score)| total + *score);
+
+    assert_eq!(total, evaluation.total());
+    assert_eq!(
+        evaluation.total().taper(evaluation.phase),
+        evaluation.score()
+    );
+    assert!(evaluation.to_string().contains("pawn structure"));
+}
+
+#[test]
+fn phase_should_taper_to_the_endgame() {
+    let score = PhaseScore::new(100, 200);
+
+    assert_eq!(score.taper(MAX_PHASE), 100);
+    assert_eq!(score.taper(0), 200);
+    assert_eq!(score.taper(MAX_PHASE / 2), 150);
+    assert_eq!(
+        Evaluation::new(&BoardMap::from_fen("4k3/4p3/8/8/8/8/4P3/4K3 w - - 0 1")).phase,
+        0
+    );
+}
+
+#[test]
+fn pawn_structure_should_punish_weak_pawns() {
+    let pawn_structure = |fen: &str| Evaluation::new(&BoardMap::from_fen(fen)).pawn_structure;
+
+    // doubled and isolated on the c-file against a healthy pair
+    let weak = pawn_structure("4k3/3pp3/8/8/8/2P5/2P5/4K3 w - - 0 1");
+    assert!(weak.midgame < 0 && weak.endgame < 0, "{weak:?}");
+
+    // a lone pawn on the sixth is passed, blocked by nothing
+    let passed = pawn_structure("4k3/8/3P4/8/8/8/8/4K3 w - - 0 1");
+    let blocked = pawn_structure("4k3/2p5/3P4/8/8/8/8/4K3 w - - 0 1");
+    assert!(passed.endgame > blocked.endgame, "{passed:?} {blocked:?}");
+}
+
+#[test]
+fn bishop_pair_should_count_bishops_on_both_colors() {
+    let bishop_pair = |fen: &str| Evaluation::new(&BoardMap::from_fen(fen)).bishop_pair;
+
+    assert!(bishop_pair("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1").endgame > 0);
+    assert_eq!(
+        bishop_pair("4k3/8/8/8/8/8/8/1B2KB2 w - - 0 1"),
+        PhaseScore::default()
+    );
+}
+
+#[test]
+fn king_safety_should_reward_a_pawn_shield() {
+    let king_safety = |fen: &str| Evaluation::new(&BoardMap::from_fen(fen)).king_safety;
+
+    let sheltered = king_safety("r5k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1");
+    let exposed = king_safety("r5k1/5ppp/8/8/8/5PPP/8/R5K1 w - - 0 1");
+    assert!(sheltered.midgame > exposed.midgame);
+}
+
+#[test]
+fn mobility_should_reward_active_pieces() {
+    let mobility = |fen: &str| Evaluation::new(&BoardMap::from_fen(fen)).mobility;
+
+    assert!(
+        mobility("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1").midgame
+            > mobility("4k3/8/8/8/8/8/8/N3K3 w - - 0 1").midgame
+    );
+}
+
+/// the same position with the colors swapped
+fn mirror(fen: &str) -> String {
+    let fields: Vec<&str> = fen.split(' ').collect();
+    let swap_case = |text: &str| -> String {
+        text.chars()
+            .map(|c| match c.is_ascii_uppercase() {
+                true => c.to_ascii_lowercase(),
+                false => c.to_ascii_uppercase(),
+            })
+            .collect()
+    };
+    let placement = fields[0].split('/').rev().collect::<Vec<_>>().join("/");
+    let active_color = if fields[1] == "w" { "b" } else { "w" };
+    let castling = if fields[2] == "-" {
+        "-".to_string()
+    } else {
+        let mut rights: Vec<char> = swap_case(fields[2]).chars().collect();
+        rights.sort_by_key(|c| (c.is_ascii_lowercase(), *c != 'K' && *c != 'k'));
+        rights.into_iter().collect()
+    };
+    let en_passant = match fields[3] {
+        "-" => "-".to_string(),
+        square => square
+            .chars()
+            .map(|c| match c {
+                '3' => '6',
+                '6' => '3',
+                c => c,
+            })
+            .collect(),
+    };
+    format!(
+        "{} {active_color} {castling} {en_passant} {}",
+        swap_case(&placement),
+        fields[4..].join(" ")
+    )
+}
@@ -1,6 +1,7 @@
+use check_buddy::piece_color::PieceColor;
 use check_buddy::piece_type::{BISHOP, BLACK, KING, KNIGHT, PAWN, QUEEN, ROOK, WHITE};
-use check_buddy::position_move::Position;
-use check_buddy::{BoardMap, Piece};
+use check_buddy::position_move::{Position, PositionMove};
+use check_buddy::{BoardMap, FenError, FenField, Game, Outcome, Piece};
 
 #[test]
 fn fen_to_board() {
@@ -33,7 +34,7 @@ fn complex_fen_to_board() {
 
 #[test]
 fn board_to_fen() {
-    let eventual_fen = "4k2N/1qq5/p4b2/1B6/3R2B1/1n2BqP1/8/2QK2r1";
+    let eventual_fen = "4k2N/1qq5/p4b2/1B6/3R2B1/1n2BqP1/8/2QK2r1 w - - 0 1";
     let mut board = BoardMap::empty();
     board.set_piece([0, 4], KING | BLACK);
     board.set_piece([0, 7], KNIGHT | WHITE);
@@ -55,6 +56,200 @@ fn board_to_fen() {
     assert_eq!(eventual_fen, generated_fen);
 }
 
+#[test]
+fn fen_should_round_trip() {
+    let fens = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/8/8/8/8/8/8/R3K2R b Kq - 17 42",
+        "4k3/8/8/8/8/8/8/4K3 w - - 99 120",
+    ];
+    for fen in fens {
+        assert_eq!(fen, BoardMap::from_fen(fen).get_fen());
+    }
+}
+
+#[test]
+fn fen_should_read_all_fields() {
+    let board = BoardMap::from_fen("r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 3 25");
+
+    assert_eq!(PieceColor::White, *board.get_active_color());
+    let castling_rights = board.get_castling_rights();
+    assert!(castling_rights.white_short);
+    assert!(!castling_rights.white_long);
+    assert!(!castling_rights.black_short);
+    assert!(castling_rights.black_long);
+    assert_eq!(Some([2, 3]), board.get_en_passant());
+    assert_eq!(3, board.get_halfmove_clock());
+    assert_eq!(25, board.get_fullmove_number());
+    // the en passant target makes the capture available
+    assert!(board.gen_legal_positions([3, 4]).contains(&[2, 3]));
+}
+
+#[test]
+fn missing_castling_rights_should_prevent_castling() {
+    let board = BoardMap::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1");
+    let positions = board.gen_legal_positions([7, 4]);
+
+    assert!(!positions.contains(&[7, 6]));
+    assert!(!positions.contains(&[7, 2]));
+}
+
+#[test]
+fn moves_should_update_fen_fields() {
+    let mut board = BoardMap::starting();
+    board
+        .single_move_turn(PositionMove::new([6, 4], [4, 4]))
+        .unwrap();
+    assert_eq!(
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        board.get_fen()
+    );
+
+    board
+        .single_move_turn(PositionMove::new([0, 6], [2, 5]))
+        .unwrap();
+    assert_eq!(
+        "rnbqkb1r/pppppppp/5n2/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 1 2",
+        board.get_fen()
+    );
+}
+
+#[test]
+fn try_from_fen_should_accept_valid_fens() {
+    let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    let board: BoardMap = fen.parse().unwrap();
+    assert_eq!(fen, board.get_fen());
+
+    let board = BoardMap::try_from_fen("4k3/8/8/8/8/8/8/4K3").unwrap();
+    assert_eq!("4k3/8/8/8/8/8/8/4K3 w - - 0 1", board.get_fen());
+}
+
+#[test]
+fn crowded_board_should_not_overflow_the_move_list() {
+    // no game can reach it, but it's a legal position with more moves than any real one
+    let fen = "QQQQQQQQ/Q6Q/Q6Q/Q6Q/Q6Q/QQ5Q/ppQ4Q/knQQQQQK w - - 0 1";
+    let board = BoardMap::try_from_fen(fen).unwrap();
+
+    let moves = board.gen_all_legal_moves();
+    assert_eq!(263, moves.len());
+    assert_eq!(moves, board.legal_moves().into_iter().collect::<Vec<_>>());
+    assert_eq!(Outcome::Ongoing, board.outcome());
+    assert_eq!(None, Game::new(board).result());
+}
+
+#[test]
+fn try_from_fen_should_report_malformed_fields() {
+    let cases = [
+        ("", FenError::Empty),
+        (
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 1 extra",
+            FenError::TooManyFields,
+        ),
+        ("4k3/8/8/8/8/8/4K3 w - - 0 1", FenError::InvalidRankCount(7)),
+        (
+            "4k3/8/8/8/9/8/8/4K3 w - - 0 1",
+            FenError::InvalidPiece {
+                rank: 4,
+                character: '9',
+            },
+        ),
+        (
+            "4k3/8/8/8/8/8/8/4K4 w - - 0 1",
+            FenError::InvalidRankLength { rank: 1, length: 9 },
+        ),
+        (
+            "4k3/8/8/3x4/8/8/8/4K3 w - - 0 1",
+            FenError::InvalidPiece {
+                rank: 5,
+                character: 'x',
+            },
+        ),
+        (
+            "4k3/8/8/8/8/8/8/4K3 x - - 0 1",
+            FenError::InvalidValue {
+                field: FenField::ActiveColor,
+                value: "x".to_string(),
+            },
+        ),
+        (
+            "4k3/8/8/8/8/8/8/4K3 w KX - 0 1",
+            FenError::InvalidCharacter {
+                field: FenField::Castling,
+                character: 'X',
+            },
+        ),
+        (
+            "4k3/8/8/8/8/8/8/4K3 w - e9 0 1",
+            FenError::InvalidValue {
+                field: FenField::EnPassant,
+                value: "e9".to_string(),
+            },
+        ),
+        (
+            "4k3/8/8/8/8/8/8/4K3 w - - x 1",
+            FenError::InvalidValue {
+                field: FenField::HalfmoveClock,
+                value: "x".to_string(),
+            },
+        ),
+        (
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 0",
+            FenError::InvalidValue {
+                field: FenField::FullmoveNumber,
+                value: "0".to_string(),
+            },
+        ),
+    ];
+    for (fen, error) in cases {
+        assert_eq!(Err(error), BoardMap::try_from_fen(fen).map(|_| ()), "{fen}");
+    }
+}
+
+#[test]
+fn try_from_fen_should_reject_illegal_positions() {
+    let cases = [
+        (
+            "8/8/8/8/8/8/8/4K3 w - - 0 1",
+            FenError::MissingKing(PieceColor::Black),
+        ),
+        (
+            "4k3/8/8/8/8/8/8/3KK3 w - - 0 1",
+            FenError::TooManyKings(PieceColor::White),
+        ),
+        (
+            "4k2P/8/8/8/8/8/8/4K3 w - - 0 1",
+            FenError::PawnOnBackRank { rank: 8, file: 'h' },
+        ),
+        (
+            "4k3/8/8/8/8/8/8/4K3 w K - 0 1",
+            FenError::CastlingWithoutPieces('K'),
+        ),
+        (
+            "4k3/8/8/8/4P3/8/8/4K3 b - d3 0 1",
+            FenError::InvalidValue {
+                field: FenField::EnPassant,
+                value: "d3".to_string(),
+            },
+        ),
+        (
+            "4k3/8/8/8/8/8/4r3/4K3 b - - 0 1",
+            FenError::KingCapturable(PieceColor::White),
+        ),
+    ];
+    for (fen, error) in cases {
+        assert_eq!(Err(error), BoardMap::try_from_fen(fen).map(|_| ()), "{fen}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "FEN incorrect")]
+fn from_fen_should_panic_on_malformed_fen() {
+    BoardMap::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 one");
+}
+
 fn assert_piece(board: BoardMap, pos: Position, piece_value: u32) {
     let piece = board.get_piece(pos);
     assert_eq!(Piece(piece_value), piece);
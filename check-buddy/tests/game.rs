@@ -0,0 +1,117 @@
+use check_buddy::piece_color::PieceColor;
+use check_buddy::position_move::PositionMove;
+use check_buddy::{BoardMap, Game, Outcome};
+
+fn play(game: &mut Game, moves: &[&str]) {
+    for san in moves {
+        let uci_move = game.current().parse_uci_to_move(san).unwrap();
+        game.play(uci_move).unwrap();
+    }
+}
+
+#[test]
+fn new_game_should_start_at_its_position() {
+    let board = BoardMap::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1");
+    let game = Game::new(board);
+
+    assert_eq!(board, *game.current());
+    assert_eq!(Some(&board), game.position_at(0));
+    assert_eq!(None, game.position_at(1));
+    assert!(game.moves().is_empty());
+    assert_eq!(None, game.result());
+}
+
+#[test]
+fn positions_should_follow_the_moves() {
+    let mut game = Game::default();
+    play(&mut game, &["e4", "e5", "Nf3"]);
+
+    let mut board = BoardMap::starting();
+    assert_eq!(Some(&board), game.position_at(0));
+    for (ply, &uci_move) in game.moves().iter().enumerate() {
+        board.make_move(uci_move);
+        assert_eq!(Some(&board), game.position_at(ply + 1));
+    }
+    assert_eq!(board, *game.current());
+    assert_eq!(
+        "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2",
+        game.current().get_fen()
+    );
+}
+
+#[test]
+fn illegal_moves_should_leave_the_game_alone() {
+    let mut game = Game::default();
+    play(&mut game, &["e4"]);
+
+    assert!(game.play(PositionMove::new([6, 3], [4, 3])).is_err());
+    assert!(game.play(PositionMove::new([0, 4], [2, 4])).is_err());
+    assert_eq!(1, game.moves().len());
+    assert_eq!(Some(game.current()), game.position_at(1));
+}
+
+#[test]
+fn undo_should_take_back_moves_until_the_start() {
+    let mut game = Game::default();
+    play(&mut game, &["e4", "e5"]);
+    let moves = game.moves().to_vec();
+
+    assert_eq!(Some(moves[1]), game.undo());
+    assert_eq!(Some(game.current()), game.position_at(1));
+    assert_eq!(Some(moves[0]), game.undo());
+    assert_eq!(BoardMap::starting(), *game.current());
+    assert_eq!(None, game.undo());
+    assert!(game.moves().is_empty());
+}
+
+#[test]
+fn redo_should_play_taken_back_moves_again() {
+    let mut game = Game::default();
+    play(&mut game, &["d4", "d5", "c4"]);
+    let board = *game.current();
+    let moves = game.moves().to_vec();
+
+    game.undo();
+    game.undo();
+    assert_eq!(Some(moves[1]), game.redo());
+    assert_eq!(Some(moves[2]), game.redo());
+    assert_eq!(None, game.redo());
+    assert_eq!(board, *game.current());
+    assert_eq!(moves, game.moves());
+}
+
+#[test]
+fn playing_a_move_should_forget_the_taken_back_ones() {
+    let mut game = Game::default();
+    play(&mut game, &["d4", "d5"]);
+
+    game.undo();
+    play(&mut game, &["Nf6"]);
+    assert_eq!(None, game.redo());
+    assert_eq!(2, game.moves().len());
+}
+
+#[test]
+fn result_should_follow_undo_and_redo() {
+    let mut game = Game::default();
+    play(&mut game, &["f3", "e5", "g4", "Qh4#"]);
+    assert_eq!(Some(Outcome::Checkmate(PieceColor::Black)), game.result());
+
+    game.undo();
+    assert_eq!(None, game.result());
+    game.redo();
+    assert_eq!(Some(Outcome::Checkmate(PieceColor::Black)), game.result());
+}
+
+#[test]
+fn undo_should_step_out_of_a_repetition() {
+    let shuffle = ["Nf3", "Nf6", "Ng1", "Ng8"];
+    let mut game = Game::default();
+    play(&mut game, &shuffle);
+    play(&mut game, &shuffle);
+    assert_eq!(Some(Outcome::ThreefoldRepetition), game.result());
+
+    game.undo();
+    assert_eq!(None, game.result());
+    assert!(!game.is_threefold_repetition());
+}
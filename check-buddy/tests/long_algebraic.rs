@@ -0,0 +1,70 @@
+use check_buddy::piece_type::PieceType;
+use check_buddy::position_move::PositionMove;
+use check_buddy::{BoardMap, UciMoveError};
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+#[test]
+fn every_legal_move_should_round_trip() {
+    for fen in [
+        KIWIPETE_FEN,
+        "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+    ] {
+        let board = BoardMap::from_fen(fen);
+        for position_move in board.gen_all_legal_moves() {
+            let uci = position_move.to_string();
+            assert_eq!(
+                position_move,
+                PositionMove::from_uci(&uci, &board).unwrap(),
+                "{uci}"
+            );
+        }
+    }
+}
+
+#[test]
+fn display_should_write_coordinates() {
+    assert_eq!("e2e4", PositionMove::new([6, 4], [4, 4]).to_string());
+    assert_eq!(
+        "a7a8n",
+        PositionMove::new([1, 0], [0, 0])
+            .with_promotion(PieceType::Knight)
+            .to_string()
+    );
+}
+
+#[test]
+fn board_context_should_fill_in_the_move() {
+    let board = BoardMap::from_fen("4k3/1P6/8/3pP3/8/8/8/R3K3 w Q d6 0 1");
+
+    let en_passant = PositionMove::from_uci("e5d6", &board).unwrap();
+    assert!(en_passant.is_en_passant());
+    assert!(en_passant.is_capture());
+
+    let promotion = PositionMove::from_uci("b7b8", &board).unwrap();
+    assert_eq!(Some(PieceType::Queen), promotion.promotion);
+    let promotion = PositionMove::from_uci("b7b8r", &board).unwrap();
+    assert_eq!(Some(PieceType::Rook), promotion.promotion);
+
+    let mut board = board;
+    let castling = PositionMove::from_uci("e1c1", &board).unwrap();
+    board.single_move_turn(castling).unwrap();
+    assert_eq!(Some(PieceType::Rook), board.get_piece([7, 3]).get_type());
+}
+
+#[test]
+fn invalid_coordinates_should_fail() {
+    let board = BoardMap::starting();
+    let error = |uci: &str| PositionMove::from_uci(uci, &board).unwrap_err();
+
+    assert!(matches!(error("e2"), UciMoveError::InvalidUciMove));
+    assert!(matches!(error("e2e4qq"), UciMoveError::InvalidUciMove));
+    assert!(matches!(error("é2e4"), UciMoveError::InvalidUciMove));
+    assert!(matches!(error("i2e4"), UciMoveError::FromNotFound));
+    assert!(matches!(error("e2e9"), UciMoveError::ToNotFound));
+    assert!(matches!(
+        error("e7e8k"),
+        UciMoveError::InvalidPromotionPiece('k')
+    ));
+}
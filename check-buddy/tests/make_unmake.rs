@@ -0,0 +1,90 @@
+use check_buddy::piece_color::PieceColor;
+use check_buddy::piece_type::{PieceType, BLACK, PAWN};
+use check_buddy::position_move::PositionMove;
+use check_buddy::{BoardMap, Piece};
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+#[test]
+fn make_move_should_hand_over_the_turn() {
+    let mut board = BoardMap::starting();
+    board.make_move(PositionMove::new([6, 4], [4, 4]));
+
+    assert_eq!(
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        board.get_fen()
+    );
+}
+
+#[test]
+fn unmake_should_restore_an_en_passant_capture() {
+    let mut board = BoardMap::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1");
+    let before = snapshot(&board);
+    let undo = board.make_move(PositionMove::new([3, 4], [2, 3]));
+    assert!(undo.get_move().is_en_passant());
+
+    assert_eq!(Some(Piece((PAWN | BLACK) + 32)), undo.get_captured());
+    assert_eq!("4k3/8/3P4/8/8/8/8/4K3 b - - 0 1", board.get_fen());
+
+    board.unmake_move(undo);
+    assert_eq!(before, snapshot(&board));
+}
+
+#[test]
+fn unmake_should_restore_castling_and_promotion() {
+    let mut board = BoardMap::from_fen("r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 3 7");
+    let before = snapshot(&board);
+    for position_move in [
+        PositionMove::new([7, 4], [7, 2]),
+        PositionMove::new([1, 1], [0, 0]).with_promotion(PieceType::Knight),
+    ] {
+        let undo = board.make_move(position_move);
+        board.unmake_move(undo);
+        assert_eq!(before, snapshot(&board));
+    }
+}
+
+#[test]
+fn every_move_should_be_unmade_exactly() {
+    for (fen, depth) in [
+        (KIWIPETE_FEN, 2),
+        ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 3),
+        ("n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1", 2),
+    ] {
+        let mut board = BoardMap::from_fen(fen);
+        make_unmake(&mut board, depth);
+        assert_eq!(fen, board.get_fen());
+    }
+}
+
+fn make_unmake(board: &mut BoardMap, depth: usize) {
+    if depth == 0 {
+        return;
+    }
+    let before = snapshot(board);
+    for position_move in board.gen_all_legal_moves() {
+        let undo = board.make_move(position_move);
+        make_unmake(board, depth - 1);
+        board.unmake_move(undo);
+        assert_eq!(before, snapshot(board), "after {position_move:?}");
+    }
+}
+
+/// the squares with their en passant flags, all FEN fields and the bitboards
+fn snapshot(board: &BoardMap) -> (String, Vec<u64>) {
+    let bitboards = [PieceColor::White, PieceColor::Black]
+        .into_iter()
+        .flat_map(|color| {
+            [
+                PieceType::King,
+                PieceType::Pawn(false),
+                PieceType::Knight,
+                PieceType::Bishop,
+                PieceType::Rook,
+                PieceType::Queen,
+            ]
+            .map(|piece_type| board.get_bitboard(color, piece_type))
+        })
+        .collect();
+    (format!("{board:?}"), bitboards)
+}
@@ -0,0 +1,169 @@
+use check_buddy::piece_color::PieceColor;
+use check_buddy::position_move::PositionMove;
+use check_buddy::{BoardMap, Game, Outcome};
+
+#[test]
+fn starting_position_should_be_ongoing() {
+    let outcome = BoardMap::starting().outcome();
+
+    assert_eq!(Outcome::Ongoing, outcome);
+    assert!(!outcome.is_over());
+}
+
+#[test]
+fn fools_mate_should_be_checkmate() {
+    let mut game = Game::default();
+    for piece_move in [
+        PositionMove::new([6, 5], [5, 5]),
+        PositionMove::new([1, 4], [3, 4]),
+        PositionMove::new([6, 6], [4, 6]),
+        PositionMove::new([0, 3], [4, 7]),
+    ] {
+        game.play(piece_move).unwrap();
+    }
+
+    let status = game.status();
+    assert_eq!(Outcome::Checkmate(PieceColor::Black), status);
+    assert_eq!(Some(PieceColor::Black), status.winner());
+    assert!(!status.is_draw());
+}
+
+#[test]
+fn no_moves_without_check_should_be_stalemate() {
+    let board = BoardMap::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1");
+
+    assert_eq!(Outcome::Stalemate, board.outcome());
+    assert!(board.outcome().is_draw());
+}
+
+#[test]
+fn bare_minor_pieces_should_be_insufficient_material() {
+    for fen in [
+        "7k/8/8/8/8/8/8/K7 w - - 0 1",
+        "7k/8/8/8/8/8/8/KN6 w - - 0 1",
+        "7k/8/8/8/8/8/8/KB6 w - - 0 1",
+        // both bishops on dark squares
+        "6bk/8/8/8/8/8/8/KB6 w - - 0 1",
+    ] {
+        assert_eq!(
+            Outcome::InsufficientMaterial,
+            BoardMap::from_fen(fen).outcome(),
+            "{fen}"
+        );
+    }
+}
+
+#[test]
+fn mating_material_should_be_sufficient() {
+    for fen in [
+        "7k/8/8/8/8/8/8/KP6 w - - 0 1",
+        "7k/8/8/8/8/8/8/KR6 w - - 0 1",
+        "7k/8/8/8/8/8/8/KNN5 w - - 0 1",
+        "7k/8/8/8/8/8/8/KBN5 w - - 0 1",
+        // bishops on different square colors
+        "5b1k/8/8/8/8/8/8/KB6 w - - 0 1",
+    ] {
+        assert!(!BoardMap::from_fen(fen).is_insufficient_material(), "{fen}");
+    }
+}
+
+#[test]
+fn hundred_and_fifty_quiet_halfmoves_should_be_a_draw() {
+    let board = BoardMap::from_fen("7k/8/8/8/8/8/8/KR6 w - - 150 80");
+    assert_eq!(Outcome::FiftyMoveRule, board.outcome());
+
+    let board = BoardMap::from_fen("7k/8/8/8/8/8/8/KR6 w - - 149 80");
+    assert_eq!(Outcome::Ongoing, board.outcome());
+}
+
+#[test]
+fn fifty_moves_should_only_allow_a_claim() {
+    let game = Game::new(BoardMap::from_fen("7k/8/8/8/8/8/8/KR6 w - - 100 80"));
+
+    assert_eq!(Outcome::Ongoing, game.current().outcome());
+    assert_eq!(None, game.result());
+    assert!(game.can_claim_fifty_moves());
+}
+
+#[test]
+fn checkmate_should_win_over_the_seventy_five_move_rule_on_the_board() {
+    let board = BoardMap::from_fen("R6k/6pp/8/8/8/8/8/K7 b - - 150 80");
+
+    assert_eq!(Outcome::Checkmate(PieceColor::White), board.outcome());
+}
+
+fn play(game: &mut Game, moves: &[&str]) {
+    for san in moves {
+        let uci_move = game.current().parse_uci_to_move(san).unwrap();
+        game.play(uci_move).unwrap();
+    }
+}
+
+#[test]
+fn played_moves_should_be_remembered() {
+    let mut game = Game::default();
+    play(&mut game, &["e4", "e5", "Nf3"]);
+
+    assert_eq!(3, game.moves().len());
+    assert_eq!(4, game.position_keys().len());
+    assert_eq!(Some(&game.current().zobrist()), game.position_keys().last());
+
+    assert!(game.play(PositionMove::new([0, 4], [2, 4])).is_err());
+    assert_eq!(3, game.moves().len());
+    assert_eq!(4, game.position_keys().len());
+}
+
+#[test]
+fn knights_going_back_and_forth_should_repeat_the_position() {
+    let shuffle = ["Nf3", "Nf6", "Ng1", "Ng8"];
+    let mut game = Game::default();
+    assert_eq!(1, game.repetitions());
+
+    play(&mut game, &shuffle);
+    assert_eq!(2, game.repetitions());
+    assert!(!game.is_threefold_repetition());
+    assert_eq!(Outcome::Ongoing, game.status());
+
+    play(&mut game, &shuffle);
+    assert!(game.is_threefold_repetition());
+    assert!(!game.is_fivefold_repetition());
+    assert_eq!(Outcome::ThreefoldRepetition, game.status());
+    assert!(game.status().is_draw());
+
+    play(&mut game, &shuffle);
+    play(&mut game, &shuffle);
+    assert_eq!(5, game.repetitions());
+    assert!(game.is_fivefold_repetition());
+}
+
+#[test]
+fn pawn_moves_should_end_the_repetition_window() {
+    let mut game = Game::default();
+    play(&mut game, &["Nf3", "Nf6", "Ng1", "Ng8", "e4", "e5"]);
+    play(&mut game, &["Nf3", "Nf6", "Ng1", "Ng8"]);
+
+    assert_eq!(2, game.repetitions());
+}
+
+#[test]
+fn fifty_and_seventy_five_moves_should_count_quiet_halfmoves() {
+    let game = Game::new(BoardMap::from_fen("7k/8/8/8/8/8/8/KR6 w - - 99 80"));
+    assert!(!game.can_claim_fifty_moves());
+
+    let game = Game::new(BoardMap::from_fen("7k/8/8/8/8/8/8/KR6 w - - 100 80"));
+    assert!(game.can_claim_fifty_moves());
+    assert!(!game.is_seventy_five_move_draw());
+
+    let mut game = Game::new(BoardMap::from_fen("7k/8/8/8/8/8/8/KR6 w - - 149 80"));
+    play(&mut game, &["Rb2"]);
+    assert_eq!(150, game.halfmove_clock());
+    assert!(game.is_seventy_five_move_draw());
+}
+
+#[test]
+fn checkmate_should_win_over_the_seventy_five_move_rule() {
+    let game = Game::new(BoardMap::from_fen("R6k/6pp/8/8/8/8/8/K7 b - - 150 80"));
+
+    assert!(game.can_claim_fifty_moves());
+    assert!(!game.is_seventy_five_move_draw());
+}
@@ -0,0 +1,167 @@
+use check_buddy::{BoardMap, TranspositionTable};
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const POSITION_3_FEN: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+const POSITION_4_FEN: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+const POSITION_5_FEN: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+const POSITION_6_FEN: &str =
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+fn assert_perft(fen: &str, expected: &[u64]) {
+    let board = BoardMap::from_fen(fen);
+    for (depth, &nodes) in expected.iter().enumerate() {
+        assert_eq!(
+            nodes,
+            board.perft(depth + 1),
+            "{fen} at depth {}",
+            depth + 1
+        );
+    }
+}
+
+#[test]
+fn starting_position() {
+    assert_perft(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        &[20, 400, 8_902, 197_281],
+    );
+}
+
+#[test]
+fn kiwipete() {
+    assert_perft(KIWIPETE_FEN, &[48, 2_039, 97_862]);
+}
+
+#[test]
+fn position_3() {
+    assert_perft(POSITION_3_FEN, &[14, 191, 2_812, 43_238]);
+}
+
+#[test]
+fn position_4() {
+    assert_perft(POSITION_4_FEN, &[6, 264, 9_467]);
+}
+
+#[test]
+fn position_4_mirrored() {
+    assert_perft(
+        "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+        &[6, 264, 9_467],
+    );
+}
+
+#[test]
+fn position_5() {
+    assert_perft(POSITION_5_FEN, &[44, 1_486, 62_379]);
+}
+
+#[test]
+fn position_6() {
+    assert_perft(POSITION_6_FEN, &[46, 2_079, 89_890]);
+}
+
+#[test]
+fn en_passant_and_discovered_checks() {
+    // taking en passant would uncover the king along the diagonal or the row
+    assert_perft(
+        "8/8/4k3/8/2p5/8/B2P2K1/8 w - - 0 1",
+        &[13, 102, 1_266, 10_276],
+    );
+    assert_perft(
+        "8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1",
+        &[15, 126, 1_928, 13_931],
+    );
+    assert_perft(
+        "3k4/3p4/8/K1P4r/8/8/8/8 b - - 0 1",
+        &[18, 92, 1_670, 10_138],
+    );
+    assert_perft(
+        "8/8/1P2K3/8/2n5/1q6/8/5k2 b - - 0 1",
+        &[29, 165, 5_160, 31_961],
+    );
+}
+
+#[test]
+fn chess960_positions() {
+    assert_perft(
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        &[21, 528, 12_189],
+    );
+    assert_perft(
+        "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+        &[21, 807, 18_002],
+    );
+    assert_perft(
+        "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+        &[20, 479, 10_471],
+    );
+    assert_perft(
+        "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+        &[22, 593, 13_440],
+    );
+    assert_perft(
+        "1nbbnrkr/p1p1ppp1/3p4/1p3P1p/3Pq2P/8/PPP1P1P1/QNBBNRKR w HFhf - 0 9",
+        &[28, 1_120, 31_058],
+    );
+}
+
+#[test]
+fn kiwipete_as_chess960() {
+    let mut board = BoardMap::from_fen(KIWIPETE_FEN);
+    board.set_chess960(true);
+    for (depth, nodes) in [48, 2_039, 97_862].into_iter().enumerate() {
+        assert_eq!(nodes, board.perft(depth + 1));
+    }
+}
+
+#[test]
+fn divide_should_add_up_to_perft() {
+    let board = BoardMap::from_fen(KIWIPETE_FEN);
+    let divide = board.perft_divide(2);
+
+    assert_eq!(48, divide.len());
+    assert_eq!(2_039, divide.iter().map(|(_, nodes)| nodes).sum::<u64>());
+}
+
+#[test]
+fn perft_with_table_should_count_the_same() {
+    let mut table = TranspositionTable::new(1);
+    for (fen, depth, nodes) in [
+        (KIWIPETE_FEN, 3, 97_862),
+        (POSITION_3_FEN, 4, 43_238),
+        (POSITION_5_FEN, 3, 62_379),
+    ] {
+        table.clear();
+        let board = BoardMap::from_fen(fen);
+        assert_eq!(nodes, board.perft_with_table(depth, &mut table), "{fen}");
+        // a second run is answered from the table
+        assert_eq!(nodes, board.perft_with_table(depth, &mut table), "{fen}");
+    }
+}
+
+#[test]
+#[ignore = "slow in a debug build, run with `cargo test --release -- --ignored`"]
+fn deep_reference_positions() {
+    assert_perft(KIWIPETE_FEN, &[48, 2_039, 97_862, 4_085_603]);
+    assert_perft(
+        POSITION_3_FEN,
+        &[14, 191, 2_812, 43_238, 674_624, 11_030_083],
+    );
+    assert_perft(POSITION_4_FEN, &[6, 264, 9_467, 422_333]);
+    assert_perft(POSITION_5_FEN, &[44, 1_486, 62_379, 2_103_487]);
+    assert_perft(POSITION_6_FEN, &[46, 2_079, 89_890, 3_894_594]);
+    for (fen, depth, nodes) in [
+        ("8/8/4k3/8/2p5/8/B2P2K1/8 w - - 0 1", 6, 1_015_133),
+        ("8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1", 6, 1_440_467),
+        ("5k2/8/8/8/8/8/8/4K2R w K - 0 1", 6, 661_072),
+        ("3k4/3p4/8/K1P4r/8/8/8/8 b - - 0 1", 6, 1_134_888),
+        ("8/8/1P2K3/8/2n5/1q6/8/5k2 b - - 0 1", 5, 1_004_658),
+        ("4k3/1P6/8/8/8/8/K7/8 w - - 0 1", 6, 217_342),
+        ("8/P1k5/K7/8/8/8/8/8 w - - 0 1", 6, 92_683),
+        ("K1k5/8/P7/8/8/8/8/8 w - - 0 1", 6, 2_217),
+        ("8/k1P5/8/1K6/8/8/8/8 w - - 0 1", 7, 567_584),
+        ("8/8/2k5/5q2/5n2/8/5K2/8 b - - 0 1", 4, 23_527),
+    ] {
+        assert_eq!(nodes, BoardMap::from_fen(fen).perft(depth), "{fen}");
+    }
+}
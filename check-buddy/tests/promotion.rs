@@ -0,0 +1,75 @@
+use check_buddy::piece_type::{PieceType, BLACK, KNIGHT, QUEEN, ROOK, WHITE};
+use check_buddy::position_move::{PositionMove, PROMOTION_PIECES};
+use check_buddy::{BoardMap, Piece};
+
+const PROMOTION_FEN: &str = "1r5k/P7/8/8/8/8/8/7K w - - 0 1";
+
+#[test]
+fn every_promotion_piece_should_be_generated() {
+    let board = BoardMap::from_fen(PROMOTION_FEN);
+    let moves = board.gen_all_legal_moves();
+
+    for to in [[0, 0], [0, 1]] {
+        let promotions = moves
+            .iter()
+            .filter(|m| m.from == [1, 0] && m.to == to)
+            .map(|m| m.promotion)
+            .collect::<Vec<_>>();
+        assert_eq!(4, promotions.len());
+        for piece_type in PROMOTION_PIECES {
+            assert!(promotions.contains(&Some(piece_type)));
+        }
+    }
+}
+
+#[test]
+fn promotion_should_use_the_chosen_piece() {
+    let mut board = BoardMap::from_fen(PROMOTION_FEN);
+    board
+        .single_move_turn(PositionMove::new([1, 0], [0, 1]).with_promotion(PieceType::Knight))
+        .unwrap();
+
+    assert_eq!(Piece(KNIGHT | WHITE), board.get_piece([0, 1]));
+    assert_eq!("1N5k/8/8/8/8/8/8/7K b - - 0 1", board.get_fen());
+}
+
+#[test]
+fn promotion_without_a_piece_should_become_a_queen() {
+    let mut board = BoardMap::from_fen(PROMOTION_FEN);
+    board
+        .single_move_turn(PositionMove::new([1, 0], [0, 0]))
+        .unwrap();
+
+    assert_eq!(Piece(QUEEN | WHITE), board.get_piece([0, 0]));
+}
+
+#[test]
+fn black_should_underpromote_too() {
+    let mut board = BoardMap::from_fen("7k/8/8/8/8/8/p7/7K b - - 0 1");
+    board
+        .single_move_turn(PositionMove::new([6, 0], [7, 0]).with_promotion(PieceType::Rook))
+        .unwrap();
+
+    assert_eq!(Piece(ROOK | BLACK), board.get_piece([7, 0]));
+}
+
+#[test]
+fn invalid_promotions_should_be_refused() {
+    let mut board = BoardMap::from_fen(PROMOTION_FEN);
+
+    assert!(board
+        .single_move_turn(PositionMove::new([1, 0], [0, 0]).with_promotion(PieceType::King))
+        .is_err());
+    assert!(board
+        .single_move_turn(PositionMove::new([7, 7], [6, 7]).with_promotion(PieceType::Queen))
+        .is_err());
+}
+
+#[test]
+fn uci_promotion_should_use_the_parsed_piece() {
+    let mut board = BoardMap::from_fen("7k/P7/8/8/8/8/8/7K w - - 0 1");
+    let uci_move = board.parse_uci_to_move("a8=R").unwrap();
+    board.uci_move_turn(uci_move).unwrap();
+
+    assert_eq!(Piece(ROOK | WHITE), board.get_piece([0, 0]));
+}
@@ -0,0 +1,132 @@
+use check_buddy::piece_type::PieceType;
+use check_buddy::position_move::PositionMove;
+use check_buddy::{BoardMap, UciMoveError};
+
+#[test]
+fn san_should_write_piece_moves_and_captures() {
+    let board =
+        BoardMap::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/3PP3/5N2/PPP2PPP/RNBQKB1R b KQkq - 0 3");
+
+    assert_eq!("Nf6", san(&board, "g8f6"));
+    assert_eq!("exd4", san(&board, "e5d4"));
+    assert_eq!("Nxd4", san(&board, "c6d4"));
+    assert_eq!("Bb4+", san(&board, "f8b4"));
+}
+
+#[test]
+fn san_should_disambiguate_as_little_as_possible() {
+    // knights on b1 and f3 reach d2, rooks on a1 and a5 reach a3, queens on h4, e4 and h1 reach e1
+    let board = BoardMap::from_fen("2k5/8/8/R7/4Q2Q/5N2/2K5/RN5Q w - - 0 1");
+
+    assert_eq!("Nbd2", san(&board, "b1d2"));
+    assert_eq!("Nfd2", san(&board, "f3d2"));
+    assert_eq!("R1a3", san(&board, "a1a3"));
+    assert_eq!("R5a3", san(&board, "a5a3"));
+    assert_eq!("Qh4e1", san(&board, "h4e1"));
+    assert_eq!("Qee1", san(&board, "e4e1"));
+    assert_eq!("Q1e1", san(&board, "h1e1"));
+}
+
+#[test]
+fn san_should_write_special_moves() {
+    let board = BoardMap::from_fen("r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1");
+
+    assert_eq!("O-O", san(&board, "e1g1"));
+    assert_eq!("O-O-O", san(&board, "e1c1"));
+    assert_eq!("exd6", san(&board, "e5d6"));
+    assert_eq!("b8=Q+", san(&board, "b7b8"));
+    assert_eq!("bxa8=N", san(&board, "b7a8n"));
+    assert_eq!("Rxa8+", san(&board, "a1a8"));
+}
+
+#[test]
+fn san_should_mark_checkmate() {
+    let board = BoardMap::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+    assert_eq!("Ra8#", san(&board, "a1a8"));
+
+    let board = BoardMap::from_fen("6k1/5ppp/8/8/8/8/8/R3K2R w K - 0 1");
+    assert_eq!("Kf1", san(&board, "e1f1"));
+}
+
+#[test]
+fn san_should_parse_back_to_the_same_move() {
+    let board =
+        BoardMap::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+    for position_move in board.gen_all_legal_moves() {
+        let san = board.to_san(position_move);
+        let uci_move = board.parse_uci_to_move(&san).unwrap();
+        assert_eq!(position_move, uci_move, "{san}");
+    }
+}
+
+#[test]
+fn parser_should_accept_common_spellings() {
+    let board = BoardMap::from_fen("r3k2r/1P6/8/3pP3/8/5N2/8/R3K2R w KQkq d6 0 1");
+    let parse = |san: &str| board.parse_uci_to_move(san).unwrap();
+
+    assert_eq!(uci(&board, "b7b8q"), parse("b8=Q"));
+    assert_eq!(uci(&board, "b7b8q"), parse("b8Q"));
+    assert_eq!(uci(&board, "b7b8q"), parse("b8"));
+    assert_eq!(uci(&board, "b7a8n"), parse("bxa8=N+"));
+    assert_eq!(uci(&board, "e5d6"), parse("exd6"));
+    assert_eq!(uci(&board, "f3g5"), parse("Ng5!?"));
+    assert_eq!(uci(&board, "e1g1"), parse("0-0"));
+    assert_eq!(uci(&board, "e1c1"), parse("O-O-O"));
+    assert_eq!(uci(&board, "a1a8"), parse("Rxa8+"));
+
+    let rook_takes = parse("Rxa8+");
+    assert_eq!(Some(PieceType::Rook), rook_takes.piece.get_type());
+    assert_eq!(Some(PieceType::Rook), rook_takes.captured.get_type());
+    assert!(parse("exd6").is_en_passant());
+    assert!(parse("O-O").is_castle());
+    assert_eq!(Some(PieceType::Rook), parse("b8=R").promotion);
+}
+
+#[test]
+fn parser_should_refuse_ambiguous_moves() {
+    let board = BoardMap::from_fen("2k5/8/8/R7/4Q2Q/5N2/2K5/RN5Q w - - 0 1");
+    let parse = |san: &str| board.parse_uci_to_move(san);
+
+    assert!(matches!(parse("Nd2"), Err(UciMoveError::AmbiguousMove(_))));
+    assert!(matches!(parse("Ra3"), Err(UciMoveError::AmbiguousMove(_))));
+    assert!(matches!(parse("Qe1"), Err(UciMoveError::AmbiguousMove(_))));
+    assert!(matches!(parse("Qhe1"), Err(UciMoveError::AmbiguousMove(_))));
+    assert_eq!(uci(&board, "b1d2"), parse("Nbd2").unwrap());
+    assert_eq!(uci(&board, "a5a3"), parse("R5a3").unwrap());
+    assert_eq!(uci(&board, "h4e1"), parse("Qh4e1").unwrap());
+}
+
+#[test]
+fn parser_should_reject_malformed_moves() {
+    let board = BoardMap::starting();
+    let error = |san: &str| board.parse_uci_to_move(san).unwrap_err();
+
+    assert!(matches!(error(""), UciMoveError::Empty));
+    assert!(matches!(error("+"), UciMoveError::Empty));
+    assert!(matches!(error("a"), UciMoveError::InvalidUciMove));
+    assert!(matches!(error("N"), UciMoveError::InvalidUciMove));
+    assert!(matches!(error("Kz9"), UciMoveError::ToNotFound));
+    assert!(matches!(error("é4"), UciMoveError::InvalidUciMove));
+    assert!(matches!(error("Nzxf3"), UciMoveError::InvalidUciMove));
+    assert!(matches!(error("ed5"), UciMoveError::InvalidUciMove));
+    assert!(matches!(error("e2e4"), UciMoveError::InvalidUciMove));
+    assert!(matches!(
+        error("e8=K"),
+        UciMoveError::InvalidPromotionPiece('K')
+    ));
+    assert!(matches!(error("e5"), UciMoveError::FromNotFound));
+    assert!(matches!(error("O-O"), UciMoveError::FromNotFound));
+    assert!(matches!(error("e4=Q"), UciMoveError::FromNotFound));
+    assert!(matches!(error("Nf4"), UciMoveError::FromNotFound));
+    // f3 is empty, so the knight can't take anything there
+    assert!(matches!(error("Nxf3"), UciMoveError::FromNotFound));
+}
+
+fn uci(board: &BoardMap, uci: &str) -> PositionMove {
+    PositionMove::from_uci(uci, board).unwrap()
+}
+
+fn san(board: &BoardMap, uci: &str) -> String {
+    let position_move = PositionMove::from_uci(uci, board).unwrap();
+    board.to_san(position_move)
+}
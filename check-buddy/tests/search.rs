@@ -0,0 +1,200 @@
+use check_buddy::search_limits::SearchLimits;
+use check_buddy::{BoardMap, Game, Searcher, MATE_SCORE, MAX_DEPTH};
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+#[test]
+fn search_should_take_a_hanging_queen() {
+    let board = BoardMap::from_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1");
+    let result = Searcher::new().best_move(&board, SearchLimits::default().with_depth(2));
+
+    assert_eq!(Some([6, 3]), result.best_move.map(|m| m.from));
+    assert_eq!(Some([3, 3]), result.best_move.map(|m| m.to));
+    // a rook up, give or take the position
+    assert!((400..700).contains(&result.score), "{}", result.score);
+}
+
+#[test]
+fn quiescence_should_see_the_recapture() {
+    // Qxd5 wins a pawn one ply deep, but exd5 takes the queen back
+    let board = BoardMap::from_fen("4k3/8/4p3/3p4/8/8/8/3QK3 w - - 0 1");
+    let result = Searcher::new().best_move(&board, SearchLimits::default().with_depth(1));
+
+    assert_ne!(Some([3, 3]), result.best_move.map(|m| m.to));
+    assert!(result.score > 0);
+}
+
+#[test]
+fn captures_should_only_hold_captures_and_promotions() {
+    let board = BoardMap::from_fen(KIWIPETE_FEN);
+    let captures = board.gen_all_legal_captures();
+    assert_eq!(8, captures.len());
+    assert!(captures.iter().all(|&m| board.is_capture(m)));
+
+    let board = BoardMap::from_fen("n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1");
+    let captures = board.gen_all_legal_captures();
+    let moves = board.gen_all_legal_moves();
+    assert!(captures
+        .iter()
+        .all(|&m| board.is_capture(m) || m.promotion.is_some()));
+    assert_eq!(
+        moves
+            .iter()
+            .filter(|&&m| board.is_capture(m) || m.promotion.is_some())
+            .count(),
+        captures.len()
+    );
+    assert!(captures
+        .iter()
+        .any(|m| m.promotion.is_some() && !board.is_capture(*m)));
+}
+
+#[test]
+fn search_should_find_a_mate_in_two() {
+    let board =
+        BoardMap::from_fen("r2qkb1r/pp2nppp/3p4/2pNN1B1/2BnP3/3P4/PPP2PPP/R2bK2R w KQkq - 1 1");
+    let result = Searcher::new().best_move(&board, SearchLimits::default().with_depth(4));
+
+    assert_eq!(MATE_SCORE - 3, result.score);
+    assert_eq!(Some(2), result.mate_in());
+    assert_eq!(3, result.pv.len());
+    assert_eq!(result.best_move, result.pv.first().copied());
+    // Nf6+
+    assert_eq!([3, 3], result.pv[0].from);
+    assert_eq!([2, 5], result.pv[0].to);
+}
+
+#[test]
+fn search_should_see_being_mated() {
+    let board = BoardMap::from_fen("6k1/5ppp/8/8/8/8/r7/1r4K1 w - - 0 1");
+    let result = Searcher::new().best_move(&board, SearchLimits::default().with_depth(3));
+
+    assert_eq!(-MATE_SCORE, result.score);
+    assert_eq!(None, result.best_move);
+}
+
+#[test]
+fn search_should_report_every_iteration() {
+    let mut iterations = vec![];
+    let result = Searcher::new().search(
+        &BoardMap::starting(),
+        SearchLimits::default().with_depth(3),
+        |result| iterations.push(result.clone()),
+    );
+
+    assert_eq!(
+        vec![1, 2, 3],
+        iterations.iter().map(|r| r.depth).collect::<Vec<_>>()
+    );
+    assert_eq!(result.pv, iterations[2].pv);
+    assert_eq!(None, result.mate_in());
+}
+
+#[test]
+fn stop_flag_should_end_the_search() {
+    let mut searcher = Searcher::new();
+    let stop = searcher.stop_flag();
+    let handle =
+        thread::spawn(move || searcher.best_move(&BoardMap::starting(), SearchLimits::default()));
+    thread::sleep(Duration::from_millis(50));
+    stop.store(true, Ordering::Relaxed);
+    let result = handle.join().unwrap();
+
+    assert!(result.best_move.is_some());
+    assert!(result.depth < MAX_DEPTH);
+}
+
+#[test]
+fn stalemate_should_score_a_draw() {
+    let board = BoardMap::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1");
+    let result = Searcher::new().best_move(&board, SearchLimits::default().with_depth(3));
+
+    assert_eq!(0, result.score);
+    assert_eq!(None, result.best_move);
+}
+
+#[test]
+fn repeating_a_position_should_score_a_draw() {
+    // a rook down, black's only way out is going back to h8, which was on the board before
+    let mut game = Game::new(BoardMap::from_fen("6k1/8/8/8/8/8/8/R5K1 b - - 0 1"));
+    for san in ["Kh8", "Rb1", "Kg8", "Ra1"] {
+        let uci_move = game.current().parse_uci_to_move(san).unwrap();
+        game.play(uci_move).unwrap();
+    }
+    let limits = SearchLimits::default().with_depth(2);
+
+    let result = Searcher::new().best_move(game.current(), limits);
+    assert!(result.score < -300, "{}", result.score);
+
+    let mut searcher = Searcher::new();
+    searcher.set_history(&game.position_keys());
+    let result = searcher.best_move(game.current(), limits);
+    assert_eq!(0, result.score);
+    assert_eq!(Some([0, 7]), result.best_move.map(|m| m.to));
+}
+
+#[test]
+fn search_should_respect_the_limits() {
+    let board = BoardMap::starting();
+    let mut searcher = Searcher::new();
+
+    let result = searcher.best_move(&board, SearchLimits::default().with_depth(3));
+    assert_eq!(3, result.depth);
+    assert!(result.nodes > 0);
+    assert!(result.best_move.is_some());
+
+    let result = searcher.best_move(
+        &board,
+        SearchLimits::default().with_time(Duration::from_millis(50)),
+    );
+    assert!(result.best_move.is_some());
+
+    let result = searcher.best_move(&board, SearchLimits::default().with_nodes(2_000));
+    assert!(result.nodes < 4_000);
+    assert!(result.best_move.is_some());
+}
+
+#[test]
+fn a_second_search_should_reuse_the_table() {
+    let board = BoardMap::from_fen(KIWIPETE_FEN);
+    let limits = SearchLimits::default().with_depth(4);
+    let mut searcher = Searcher::new();
+    searcher.set_hash_size(4);
+
+    let first = searcher.best_move(&board, limits);
+    let second = searcher.best_move(&board, limits);
+    assert_eq!(first.score, second.score);
+    assert!(second.nodes < first.nodes);
+
+    searcher.clear_hash();
+    assert_eq!(first.nodes, searcher.best_move(&board, limits).nodes);
+}
+
+#[test]
+fn material_weight_should_count_both_sides() {
+    let board = BoardMap::from_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1");
+    assert_eq!(-4, board.get_material_weight());
+
+    let board = BoardMap::from_fen("4k3/8/8/3q4/8/8/3R4/4K3 b - - 0 1");
+    assert_eq!(4, board.get_material_weight());
+    assert_eq!(0, BoardMap::starting().get_material_weight());
+}
+
+#[test]
+fn deep_search_should_fit_a_small_stack() {
+    // a pawn ending goes deep quickly, a tenth of the stack a thread gets by default is plenty
+    let handle = thread::Builder::new()
+        .stack_size(200 * 1024)
+        .spawn(|| {
+            let board = BoardMap::from_fen("8/k7/3p4/p2P1p2/P2P1P2/8/8/K7 w - - 0 1");
+            Searcher::new().best_move(&board, SearchLimits::default().with_depth(16))
+        })
+        .unwrap();
+    let result = handle.join().unwrap();
+
+    assert_eq!(16, result.depth);
+    assert!(result.best_move.is_some());
+}
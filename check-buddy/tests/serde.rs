@@ -0,0 +1,165 @@
+use check_buddy::piece_color::PieceColor;
+use check_buddy::piece_type::PieceType;
+use check_buddy::position_move::PositionMove;
+use check_buddy::{BoardMap, Game, Outcome, Piece};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+const POSITIONS: [&str; 4] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 b - - 12 40",
+];
+
+/// goes through both JSON and bincode and checks both give the value back
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) {
+    let json = serde_json::to_string(value).unwrap();
+    assert_eq!(*value, serde_json::from_str::<T>(&json).unwrap(), "{json}");
+    let bytes = bincode::serialize(value).unwrap();
+    assert_eq!(*value, bincode::deserialize::<T>(&bytes).unwrap());
+}
+
+#[test]
+fn board_should_round_trip() {
+    for fen in POSITIONS {
+        let board = BoardMap::from_fen(fen);
+        round_trip(&board);
+        assert_eq!(
+            fen,
+            bincode::deserialize::<BoardMap>(&bincode::serialize(&board).unwrap())
+                .unwrap()
+                .get_fen()
+        );
+    }
+}
+
+#[test]
+fn board_should_be_written_as_fen() {
+    let board = BoardMap::starting();
+    assert_eq!(
+        format!("\"{}\"", board.get_fen()),
+        serde_json::to_string(&board).unwrap()
+    );
+}
+
+#[test]
+fn unparsable_board_should_be_refused() {
+    assert!(serde_json::from_str::<BoardMap>("\"not a fen\"").is_err());
+    assert!(serde_json::from_str::<BoardMap>("\"9/8/8/8/8/8/8/8 w - - 0 1\"").is_err());
+}
+
+#[test]
+fn illegal_board_should_round_trip() {
+    let board = BoardMap::empty();
+    assert_eq!(
+        "\"8/8/8/8/8/8/8/8 w - - 0 1\"",
+        serde_json::to_string(&board).unwrap()
+    );
+    round_trip(&board);
+    assert!(board.validate().is_err());
+}
+
+#[test]
+fn moves_should_round_trip() {
+    for fen in POSITIONS {
+        let board = BoardMap::from_fen(fen);
+        for position_move in board.gen_all_legal_moves() {
+            round_trip(&position_move);
+            let json = serde_json::to_string(&position_move).unwrap();
+            let read = serde_json::from_str::<PositionMove>(&json).unwrap();
+            assert_eq!(position_move.piece, read.piece);
+            assert_eq!(position_move.captured, read.captured);
+            assert_eq!(position_move.flags, read.flags);
+        }
+    }
+}
+
+#[test]
+fn pieces_should_round_trip() {
+    for piece_type in [
+        PieceType::King,
+        PieceType::Pawn(false),
+        PieceType::Pawn(true),
+        PieceType::Knight,
+        PieceType::Bishop,
+        PieceType::Rook,
+        PieceType::Queen,
+    ] {
+        round_trip(&piece_type);
+    }
+    round_trip(&PieceColor::White);
+    round_trip(&PieceColor::Black);
+    let board = BoardMap::from_fen(POSITIONS[2]);
+    for row in 0..8 {
+        for col in 0..8 {
+            round_trip(&board.get_piece([row, col]));
+        }
+    }
+    round_trip(&Piece::default());
+}
+
+#[test]
+fn game_should_round_trip() {
+    let mut game = Game::default();
+    game.info
+        .insert("Event".to_string(), "Casual game".to_string());
+    game.info
+        .insert("White".to_string(), "Anderssen".to_string());
+    for san in ["f3", "e5", "g4", "Qh4#"] {
+        let uci_move = game.current().parse_uci_to_move(san).unwrap();
+        game.play(uci_move).unwrap();
+    }
+    assert_eq!(Some(Outcome::Checkmate(PieceColor::Black)), game.result());
+
+    let json = serde_json::to_string(&game).unwrap();
+    let bytes = bincode::serialize(&game).unwrap();
+    for mut read in [
+        serde_json::from_str::<Game>(&json).unwrap(),
+        bincode::deserialize::<Game>(&bytes).unwrap(),
+    ] {
+        assert_eq!(game.info, read.info);
+        assert_eq!(game.position_keys(), read.position_keys());
+        assert_eq!(game.moves(), read.moves());
+        assert_eq!(game.result(), read.result());
+        // taken back moves come along too
+        read.undo();
+        let mut read =
+            serde_json::from_str::<Game>(&serde_json::to_string(&read).unwrap()).unwrap();
+        assert_eq!(game.moves().last().copied(), read.redo());
+    }
+}
+
+#[test]
+fn game_from_an_illegal_position_should_round_trip() {
+    let game = Game::new(BoardMap::from_fen("4k3/8/8/8/8/8/8/8 w - - 0 1"));
+    assert!(game.current().validate().is_err());
+
+    let json = serde_json::to_string(&game).unwrap();
+    let read = serde_json::from_str::<Game>(&json).unwrap();
+    assert_eq!(game.position_keys(), read.position_keys());
+    assert_eq!(game.result(), read.result());
+}
+
+#[test]
+fn game_without_positions_should_be_refused() {
+    let json =
+        r#"{"info":{},"positions":[],"historical_moves":[],"undone_moves":[],"result":null}"#;
+    assert!(serde_json::from_str::<Game>(json).is_err());
+}
+
+#[test]
+fn game_result_should_follow_from_its_moves() {
+    let json = r#"{"info":{},"positions":["rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"],"historical_moves":[],"undone_moves":[],"result":{"Checkmate":"White"}}"#;
+    let game = serde_json::from_str::<Game>(json).unwrap();
+    assert_eq!(None, game.result());
+    assert_eq!(Outcome::Ongoing, game.status());
+}
+
+#[test]
+fn game_with_illegal_move_should_be_refused() {
+    // e2 to e5 in one go
+    let json = r#"{"info":{},"positions":["rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"],"historical_moves":[{"from":[6,4],"to":[3,4],"piece":10,"captured":0,"promotion":null,"flags":0}],"undone_moves":[],"result":null}"#;
+    assert!(serde_json::from_str::<Game>(json).is_err());
+}
@@ -39,12 +39,7 @@ fn move_integration(board_map: BoardMap, depth: usize) -> usize {
     for (from, to) in positions {
         let mut board_map = board_map;
         if board_map
-            .single_move_turn(PositionMove {
-                from,
-                to,
-                en_passant: false,
-                promotion: false,
-            })
+            .single_move_turn(PositionMove::new(from, to))
             .is_ok()
         {
             num_moves += move_integration(board_map, depth - 1);
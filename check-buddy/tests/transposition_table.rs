@@ -0,0 +1,61 @@
+use check_buddy::TranspositionTable;
+
+#[test]
+fn size_should_follow_the_megabytes() {
+    let small = TranspositionTable::<u64>::new(1);
+    let large = TranspositionTable::<u64>::new(4);
+
+    assert!(small.capacity() > 1);
+    assert_eq!(small.capacity() * 4, large.capacity());
+    assert_eq!(1, TranspositionTable::<u64>::new(0).capacity());
+}
+
+#[test]
+fn entries_should_only_be_found_by_their_key() {
+    let mut table = TranspositionTable::new(1);
+    table.insert(42, 3, 7_u64);
+
+    assert_eq!(Some(7), table.get(42).map(|entry| entry.value));
+    assert_eq!(Some(3), table.get(42).map(|entry| entry.depth));
+    assert!(table.get(43).is_none());
+}
+
+#[test]
+fn deeper_entries_should_be_kept() {
+    // a single slot, so every key collides
+    let mut table = TranspositionTable::new(0);
+    table.insert(1, 5, 1_u64);
+    table.insert(2, 3, 2);
+    assert_eq!(Some(1), table.get(1).map(|entry| entry.value));
+
+    table.insert(2, 6, 2);
+    assert_eq!(Some(2), table.get(2).map(|entry| entry.value));
+    assert!(table.get(1).is_none());
+
+    // the same position is always updated
+    table.insert(2, 1, 3);
+    assert_eq!(Some(3), table.get(2).map(|entry| entry.value));
+}
+
+#[test]
+fn entries_of_an_earlier_search_should_be_replaced() {
+    let mut table = TranspositionTable::new(0);
+    table.insert(1, 5, 1_u64);
+    table.new_search();
+    table.insert(2, 1, 2);
+
+    assert_eq!(Some(2), table.get(2).map(|entry| entry.value));
+}
+
+#[test]
+fn clear_should_empty_the_table() {
+    let mut table = TranspositionTable::new(1);
+    for key in 0..2_000 {
+        table.insert(key, 1, key);
+    }
+    assert!(table.hashfull() > 0);
+
+    table.clear();
+    assert_eq!(0, table.hashfull());
+    assert!(table.get(1).is_none());
+}
@@ -18,18 +18,17 @@ fn uci_moves_should_be_valid() -> Result<()> {
 "
                 )
             });
-            let uci_move = actual_move.0;
-            let PositionMove { from, to, .. } = actual_move.1;
+            let PositionMove { from, to, .. } = actual_move;
             let positions = board.gen_legal_positions(from);
 
-            if !positions.iter().any(|piece_to| *piece_to == to) {
+            if !positions.contains(&to) {
                 let piece = board.get_piece(from);
                 panic!(
                     "
 {board:?}
     row {row}
     Game {id}: ({move_name})
-    Uci {uci_move:?} is invalid
+    Uci {actual_move:?} is invalid
     Move {piece_move} is invalid
     Moving piece {piece:?} from {from:?} to {to:?} isn't seen as a valid move
     Legal positions: {positions:?}
@@ -0,0 +1,76 @@
+use check_buddy::position_move::PositionMove;
+use check_buddy::BoardMap;
+use std::collections::HashSet;
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+#[test]
+fn incremental_key_should_match_a_fresh_board() {
+    for (fen, depth) in [
+        (KIWIPETE_FEN, 2),
+        ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 3),
+        ("n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1", 2),
+    ] {
+        let mut board = BoardMap::from_fen(fen);
+        assert_keys(&mut board, depth);
+    }
+}
+
+#[test]
+fn transpositions_should_hash_equal() {
+    let mut board = BoardMap::starting();
+    for position_move in [
+        PositionMove::new([7, 6], [5, 5]),
+        PositionMove::new([0, 6], [2, 5]),
+        PositionMove::new([5, 5], [7, 6]),
+        PositionMove::new([2, 5], [0, 6]),
+    ] {
+        board.single_move_turn(position_move).unwrap();
+    }
+
+    assert_eq!(BoardMap::starting().zobrist(), board.zobrist());
+    assert_eq!(BoardMap::starting(), board);
+    assert!(HashSet::from([BoardMap::starting()]).contains(&board));
+}
+
+#[test]
+fn key_should_cover_side_castling_and_en_passant() {
+    let key = |fen: &str| BoardMap::from_fen(fen).zobrist();
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR";
+
+    assert_ne!(
+        key(&format!("{start} w KQkq -")),
+        key(&format!("{start} b KQkq -"))
+    );
+    assert_ne!(
+        key(&format!("{start} w KQkq -")),
+        key(&format!("{start} w Kkq -"))
+    );
+    assert_ne!(
+        key("4k3/8/8/3pP3/8/8/8/4K3 w - d6"),
+        key("4k3/8/8/3pP3/8/8/8/4K3 w - -")
+    );
+    // nothing can take on d6, so it is the same position
+    assert_eq!(
+        key("4k3/8/8/3p4/8/8/8/4K3 w - d6"),
+        key("4k3/8/8/3p4/8/8/8/4K3 w - -")
+    );
+}
+
+fn assert_keys(board: &mut BoardMap, depth: usize) {
+    assert_eq!(
+        BoardMap::from_fen(board.get_fen()).zobrist(),
+        board.zobrist(),
+        "{board:?}"
+    );
+    if depth == 0 {
+        return;
+    }
+    let key = board.zobrist();
+    for position_move in board.gen_all_legal_moves() {
+        let undo = board.make_move(position_move);
+        assert_keys(board, depth - 1);
+        board.unmake_move(undo);
+        assert_eq!(key, board.zobrist());
+    }
+}
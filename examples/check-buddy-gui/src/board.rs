@@ -1,12 +1,13 @@
 use crate::states::PointerState;
+use check_buddy::piece_type::PieceType;
 use check_buddy::position_move::{Position, PositionMove};
-use check_buddy::{BoardMap, Piece};
+use check_buddy::{Game, Piece};
 use macroquad::prelude::*;
 use std::collections::HashMap;
 
 #[derive(Default)]
 pub struct Board {
-    pub(crate) board_map: BoardMap,
+    pub(crate) game: Game,
     pub(crate) board_conf: BoardConf,
     pub(crate) position_size: f32,
     pub(crate) position_padding: f32,
@@ -25,10 +26,19 @@ pub struct BoardConf {
 
 impl Board {
     pub async fn draw_board(&mut self) {
+        let board_map = *self.game.current();
+        let active_color = *board_map.get_active_color();
+        let in_check = board_map.is_in_check(active_color);
         for y in 0..8 {
             for x in 0..8 {
-                let piece = self.board_map.get_piece([y, x]);
+                let piece = board_map.get_piece([y, x]);
                 self.draw_board_square(x, y);
+                if in_check
+                    && piece.get_type() == Some(PieceType::King)
+                    && piece.get_color() == active_color
+                {
+                    self.draw_check_square(x, y);
+                }
                 if piece.is_piece() {
                     let (x_pos, y_pos) = (
                         (x as f32 * self.position_size) - self.position_padding,
@@ -48,6 +58,18 @@ impl Board {
         }
     }
 
+    /// the left arrow takes back a move and the right arrow plays it again
+    pub fn handle_history_keys(&mut self) {
+        if self.pointer_state != PointerState::Default {
+            return;
+        }
+        if is_key_pressed(KeyCode::Left) {
+            self.game.undo();
+        } else if is_key_pressed(KeyCode::Right) {
+            self.game.redo();
+        }
+    }
+
     fn handle_default_state(&mut self) {
         if !is_mouse_button_pressed(MouseButton::Left) {
             return;
@@ -59,13 +81,13 @@ impl Board {
         if x >= 8 || y >= 8 {
             return;
         }
-        let possible_piece = self.board_map.get_piece([y, x]);
-        if possible_piece.is_piece()
-            && possible_piece.get_color() == *self.board_map.get_active_color()
+        let board_map = self.game.current();
+        let possible_piece = board_map.get_piece([y, x]);
+        if possible_piece.is_piece() && possible_piece.get_color() == *board_map.get_active_color()
         {
             self.selected_piece = Some(possible_piece);
             self.selected_piece_position = Some([y, x]);
-            self.selected_piece_move_positions = Some(self.board_map.gen_legal_positions([y, x]));
+            self.selected_piece_move_positions = Some(board_map.gen_legal_positions([y, x]));
 
             self.pointer_state = PointerState::PieceSelected;
         }
@@ -82,9 +104,12 @@ impl Board {
 
             if let Some(positions) = self.selected_piece_move_positions.clone() {
                 if positions.contains(&[y, x]) {
-                    let piece_move =
-                        PositionMove::new(self.selected_piece_position.unwrap(), [y, x]);
-                    match self.board_map.single_move_turn(piece_move) {
+                    let from = self.selected_piece_position.unwrap();
+                    let mut piece_move = PositionMove::new(from, [y, x]);
+                    if self.game.current().is_promotion(from, [y, x]) {
+                        piece_move.promotion = Self::held_promotion_piece();
+                    }
+                    match self.game.play(piece_move) {
                         Ok(_) => {}
                         Err(e) => println!("Invalid move! ({})", e),
                     }
@@ -108,7 +133,7 @@ impl Board {
                     position[0] as f32 * self.position_size,
                     position[1] as f32 * self.position_size,
                 );
-                if self.board_map.get_piece(position).is_piece() {
+                if self.game.current().get_piece(position).is_piece() {
                     draw_rectangle(
                         x,
                         y,
@@ -138,6 +163,19 @@ impl Board {
             .await;
     }
 
+    /// holding N, B or R while dropping a pawn underpromotes, otherwise it becomes a queen
+    fn held_promotion_piece() -> Option<PieceType> {
+        if is_key_down(KeyCode::N) {
+            Some(PieceType::Knight)
+        } else if is_key_down(KeyCode::B) {
+            Some(PieceType::Bishop)
+        } else if is_key_down(KeyCode::R) {
+            Some(PieceType::Rook)
+        } else {
+            None
+        }
+    }
+
     fn draw_board_square(&mut self, x: usize, y: usize) {
         let (x_pos, y_pos) = (
             (x as f32 * self.position_size) - self.position_padding,
@@ -156,6 +194,20 @@ impl Board {
         );
     }
 
+    fn draw_check_square(&mut self, x: usize, y: usize) {
+        let (x_pos, y_pos) = (
+            (x as f32 * self.position_size) - self.position_padding,
+            y as f32 * self.position_size,
+        );
+        draw_rectangle(
+            x_pos,
+            y_pos,
+            self.position_size,
+            self.position_size,
+            Color::from_rgba(255, 0, 0, 100),
+        );
+    }
+
     async fn draw_piece(&mut self, piece: Piece, x_pos: f32, y_pos: f32, size: f32) {
         if !self
             .board_conf
@@ -2,12 +2,12 @@ pub(crate) mod board;
 pub(crate) mod states;
 
 use crate::board::Board;
-use check_buddy::BoardMap;
+use check_buddy::Game;
 use macroquad::prelude::*;
 
 pub async fn run() {
     let mut board = Board::default();
-    board.board_map = BoardMap::starting();
+    board.game = Game::default();
     board.board_conf.dark_square = Color::from_rgba(118, 150, 86, 255);
     board.board_conf.light_square = Color::from_rgba(238, 238, 210, 255);
 
@@ -19,6 +19,7 @@ pub async fn run() {
 
         board.draw_board().await;
         board.handle_pointer_state().await;
+        board.handle_history_keys();
 
         next_frame().await
     }
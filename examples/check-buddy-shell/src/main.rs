@@ -1,14 +1,16 @@
-use check_buddy::BoardMap;
+use check_buddy::Game;
 use std::io;
 use std::io::Write;
 
 fn main() {
-    let mut board = BoardMap::starting();
+    let mut game = Game::default();
     let mut buffer = String::new();
     let mut stdout = io::stdout();
 
     loop {
-        let _ = stdout.lock().write_all(format!("{:?}", board).as_ref());
+        let _ = stdout
+            .lock()
+            .write_all(format!("{:?}", game.current()).as_ref());
         let _ = stdout.lock().write_all("> ".as_ref());
         let _ = stdout.flush();
 
@@ -17,11 +19,28 @@ fn main() {
         stdin.read_line(&mut buffer).unwrap();
         buffer.retain(|c| !c.is_whitespace());
 
-        match board.parse_uci_to_move(&buffer) {
-            Ok(uci_move) => {
-                let _ = board.uci_move_turn(uci_move);
-            }
-            Err(e) => println!("{}", e),
+        match buffer.as_str() {
+            "undo" => match game.undo() {
+                Some(uci_move) => println!("took back {uci_move}"),
+                None => println!("Nothing to undo"),
+            },
+            "redo" => match game.redo() {
+                Some(uci_move) => println!("played {uci_move} again"),
+                None => println!("Nothing to redo"),
+            },
+            san => match game.current().parse_uci_to_move(san) {
+                Ok(uci_move) => {
+                    let san = game.current().to_san(uci_move);
+                    match game.play(uci_move) {
+                        Ok(()) => println!("{san}"),
+                        Err(e) => println!("{}", e),
+                    }
+                }
+                Err(e) => println!("{}", e),
+            },
+        }
+        if let Some(result) = game.result() {
+            println!("{result:?}");
         }
     }
 }